@@ -0,0 +1,112 @@
+//! Parsing throughput over a synthetic corpus of one million urls.
+//!
+//! Run with `cargo bench`. Criterion reports the throughput in urls per second.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use std::time::Duration;
+use url_parse::core::parse_mode::ParseMode;
+use url_parse::core::Parser;
+
+const CORPUS_SIZE: usize = 1_000_000;
+
+const SCHEMES: [&str; 5] = ["https://", "http://", "ftp://", "ssh://", ""];
+const LOGINS: [&str; 3] = ["", "user@", "user:pass@"];
+const HOSTS: [&str; 5] = [
+    "www.example.co.uk",
+    "example.com",
+    "localhost",
+    "192.168.178.242",
+    "a.b.c.example.org",
+];
+const PORTS: [&str; 3] = ["", ":443", ":8080"];
+const PATHS: [&str; 4] = ["", "/", "/blog/article/search", "/path/to/file.txt"];
+const QUERIES: [&str; 3] = ["", "?docid=720&hl=en", "?q=rust"];
+const ANCHORS: [&str; 2] = ["", "#dayone"];
+
+/// Build a deterministic corpus, mixing components with a linear congruential generator.
+fn corpus() -> Vec<String> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = |len: usize| {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 33) as usize % len
+    };
+    (0..CORPUS_SIZE)
+        .map(|_| {
+            [
+                SCHEMES[next(SCHEMES.len())],
+                LOGINS[next(LOGINS.len())],
+                HOSTS[next(HOSTS.len())],
+                PORTS[next(PORTS.len())],
+                PATHS[next(PATHS.len())],
+                QUERIES[next(QUERIES.len())],
+                ANCHORS[next(ANCHORS.len())],
+            ]
+            .concat()
+        })
+        .collect()
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let urls = corpus();
+    let legacy = Parser::new(None);
+    let whatwg = Parser::new(None).with_mode(ParseMode::Whatwg);
+
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Elements(urls.len() as u64));
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    group.bench_function("legacy", |b| {
+        b.iter(|| {
+            for url in &urls {
+                let _ = black_box(legacy.parse(black_box(url)));
+            }
+        })
+    });
+    group.bench_function("legacy_ref", |b| {
+        b.iter(|| {
+            for url in &urls {
+                let _ = black_box(legacy.parse_ref(black_box(url)));
+            }
+        })
+    });
+    group.bench_function("whatwg", |b| {
+        b.iter(|| {
+            for url in &urls {
+                let _ = black_box(whatwg.parse(black_box(url)));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn bench_accessors(c: &mut Criterion) {
+    let urls = corpus();
+    let parser = Parser::new(None);
+
+    let mut group = c.benchmark_group("accessors");
+    group.throughput(Throughput::Elements(urls.len() as u64));
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    group.bench_function("domain", |b| {
+        b.iter(|| {
+            for url in &urls {
+                black_box(parser.domain(black_box(url)));
+            }
+        })
+    });
+    group.bench_function("port", |b| {
+        b.iter(|| {
+            for url in &urls {
+                black_box(parser.port(black_box(url)));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse, bench_accessors);
+criterion_main!(benches);
@@ -1,3 +1,4 @@
+use crate::core::tokenizer::Tokens;
 use crate::core::Parser;
 
 impl Parser {
@@ -11,12 +12,15 @@ impl Parser {
     /// assert_eq!(result, "dayone");
     /// ```
     pub fn anchor<'a>(&self, input: &'a str) -> Option<&'a str> {
-        let position_anchor = input.find('#');
-        if let Some(v) = position_anchor {
-            let after = &input[v + 1..];
-            return Some(after);
-        }
-        None
+        self.anchor_from_tokens(input, &self.tokenize(input))
+    }
+
+    pub(crate) fn anchor_from_tokens<'a>(
+        &self,
+        input: &'a str,
+        tokens: &Tokens,
+    ) -> Option<&'a str> {
+        tokens.anchor.clone().map(|r| &input[r])
     }
 }
 
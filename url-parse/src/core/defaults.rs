@@ -1,9 +1,14 @@
 use std::collections::HashMap;
 
+#[cfg(feature = "iana-schemes")]
+const IANA_SCHEMES: &str = include_str!("../../data/iana_schemes.csv");
+
 /// Get the default port mappings for well-known ports.
 /// This is a convenience function to create a Parser object (via `Parser::new()`) and pass it defaults.
-pub fn default_port_mappings() -> HashMap<&'static str, (u32, &'static str)> {
-    HashMap::from([
+/// With the `iana-schemes` feature, the mappings also contain the schemes with a well-known port of a table mostly
+/// taken from the IANA registries.
+pub fn default_port_mappings() -> HashMap<&'static str, (u16, &'static str)> {
+    let mappings = HashMap::from([
         ("ftp", (21, "File Transfer Protocol")),
         ("http", (80, "Hypertext Transfer Protocol")),
         ("https", (443, "Hypertext Transfer Protocol Secure")),
@@ -11,5 +16,85 @@ pub fn default_port_mappings() -> HashMap<&'static str, (u32, &'static str)> {
         ("scp", (22, "SSH File Transfer Protocol")),
         ("sftp", (22, "SSH File Transfer Protocol")),
         ("s3", (443, "Amazon S3 File Transfer Protocol")),
-    ])
+    ]);
+    #[cfg(feature = "iana-schemes")]
+    let mappings = iana_schemes()
+        .map(|(scheme, port, description)| (scheme, (port, description)))
+        .chain(mappings)
+        .collect();
+    mappings
+}
+
+/// Well-known schemes whose urls have an opaque path instead of an authority and a hierarchical path.
+pub(crate) const OPAQUE_SCHEMES: [(&str, &str); 7] = [
+    ("about", "About Pages"),
+    ("data", "Data Urls"),
+    ("javascript", "JavaScript Code"),
+    ("mailto", "Electronic Mail Addresses"),
+    ("sms", "Short Message Service"),
+    ("tel", "Telephone Numbers"),
+    ("urn", "Uniform Resource Names"),
+];
+
+/// The entries of the embedded table, one `scheme,port,description` line each. Lines starting with `#` are comments.
+#[cfg(feature = "iana-schemes")]
+fn iana_schemes() -> impl Iterator<Item = (&'static str, u16, &'static str)> {
+    IANA_SCHEMES
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, ',');
+            let scheme = fields.next()?;
+            let port = fields.next()?.parse().ok()?;
+            Some((scheme, port, fields.next()?))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_port_mappings_works_when_typical() {
+        let mappings = default_port_mappings();
+        assert_eq!(
+            mappings["https"],
+            (443, "Hypertext Transfer Protocol Secure")
+        );
+        assert_eq!(mappings["s3"].0, 443);
+    }
+
+    #[cfg(feature = "iana-schemes")]
+    #[test]
+    fn test_default_port_mappings_works_when_iana_schemes() {
+        let mappings = default_port_mappings();
+        for (scheme, port) in [
+            ("ws", 80),
+            ("wss", 443),
+            ("git", 9418),
+            ("ldap", 389),
+            ("postgres", 5432),
+            ("redis", 6379),
+            ("mongodb", 27017),
+            ("amqp", 5672),
+            ("smtp", 25),
+            ("imap", 143),
+        ] {
+            assert_eq!(mappings[scheme].0, port, "{}", scheme);
+        }
+        assert_eq!(mappings["ssh"].1, "SSH File Transfer Protocol");
+    }
+
+    #[cfg(feature = "iana-schemes")]
+    #[test]
+    fn test_iana_schemes_works_when_every_line_parses() {
+        let lines = IANA_SCHEMES
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .count();
+        assert_eq!(iana_schemes().count(), lines);
+        assert!(iana_schemes().all(|(scheme, _, _)| {
+            crate::core::validation::validate_scheme(scheme, 0..scheme.len()).is_ok()
+        }));
+    }
 }
@@ -1,7 +1,7 @@
 use crate::core::global::Domain;
+use crate::core::host::ends_in_a_number;
+use crate::core::tokenizer::Tokens;
 use crate::core::Parser;
-use crate::utils::Utils;
-use regex::Regex;
 
 impl Parser {
     /// Extract the domain fields from the url.
@@ -20,78 +20,104 @@ impl Parser {
     /// assert_eq!(result, expected);
     /// ```
     pub fn domain<'a>(&self, input: &'a str) -> Domain<'a> {
-        let input = Utils::substring_after_login(self, input);
-        let input = Utils::substring_before_port(self, input);
-        let input = match input.find('/') {
-            Some(pos) => &input[..pos],
-            None => input,
-        };
-        return self
-            .domain_ipv4(input)
-            .or_else(|| self.subdomain_domain_top_level_domain(input))
-            .or_else(|| self.subdomain_domain(input))
-            .or_else(|| self.domain_alias(input))
-            .unwrap_or_else(Domain::empty);
+        self.domain_from_tokens(input, &self.tokenize(input))
     }
 
-    /// Mixes out the subdomain.domain part (i.e.: google.com -> subdomain(None), domain(google), top_level_domain(com))
-    fn subdomain_domain<'a>(&self, input: &'a str) -> Option<Domain<'a>> {
-        let re = Regex::new(r"(.*?)\.(.*)").unwrap();
-        let caps = re.captures(input);
+    pub(crate) fn domain_from_tokens<'a>(&self, input: &'a str, tokens: &Tokens) -> Domain<'a> {
+        self.domain_of_host(&input[tokens.host.clone()])
+    }
 
-        caps.as_ref()?;
+    /// Split an already extracted host, which may be an IP address.
+    pub(crate) fn domain_of_host<'a>(&self, input: &'a str) -> Domain<'a> {
+        self.domain_ipv6(input)
+            .or_else(|| self.domain_ipv4(input))
+            .unwrap_or_else(|| self.domain_fields(input))
+    }
 
-        let caps = caps.unwrap();
-        return Some(Domain {
-            subdomain: None,
-            domain: Some(caps.get(1).unwrap().as_str()),
-            top_level_domain: Some(caps.get(2).unwrap().as_str()),
-        });
+    /// Split an already extracted host name into its subdomain, domain and top level domain.
+    pub(crate) fn domain_fields<'a>(&self, input: &'a str) -> Domain<'a> {
+        self.domain_public_suffix(input)
+            .or_else(|| self.domain_alias(input))
+            .unwrap_or_else(Domain::empty)
     }
 
-    /// Mixes out the subdomain.domain.top_level_domain part (i.e.: www.google.com -> subdomain(www), domain(google), top_level_domain(com))
-    fn subdomain_domain_top_level_domain<'a>(&self, input: &'a str) -> Option<Domain<'a>> {
-        let re = Regex::new(r"(.*?)\.(.*)\.(.*)").unwrap();
-        let caps = re.captures(input);
+    /// Mixes out the domain in front of the public suffix (i.e.: a.b.example.co.uk -> subdomain(a.b), domain(example), top_level_domain(co.uk)).
+    /// A host which is a public suffix itself is split after its first label.
+    fn domain_public_suffix<'a>(&self, input: &'a str) -> Option<Domain<'a>> {
+        let first_dot = input.find('.')?;
+        let suffix_start = self
+            .public_suffix_list()
+            .public_suffix_start(input.strip_suffix('.').unwrap_or(input))
+            .filter(|&start| start > first_dot)
+            .unwrap_or(first_dot + 1);
+        let domain_end = suffix_start - 1;
+        let domain_start = input[..domain_end].rfind('.').map_or(0, |pos| pos + 1);
+        Some(Domain {
+            subdomain: (domain_start > 0).then(|| &input[..domain_start - 1]),
+            domain: Some(&input[domain_start..domain_end]),
+            top_level_domain: Some(&input[suffix_start..]),
+        })
+    }
 
-        caps.as_ref()?;
+    /// Extract the registrable domain (eTLD+1) of the url's host, according to the public suffix list.
+    ///
+    /// # Example
+    /// ```rust
+    /// use url_parse::core::Parser;
+    /// let input = "https://a.b.example.co.uk/blog";
+    /// let result = Parser::new(None).registrable_domain(input);
+    /// assert_eq!(result, Some("example.co.uk"));
+    /// ```
+    pub fn registrable_domain<'a>(&self, input: &'a str) -> Option<&'a str> {
+        let tokens = self.tokenize(input);
+        let host = &input[tokens.host];
+        if host.starts_with('[') || ends_in_a_number(host) {
+            return None;
+        }
+        self.public_suffix_list().registrable_domain(host)
+    }
 
-        let caps = caps.unwrap();
-        return Some(Domain {
-            subdomain: Some(caps.get(1).unwrap().as_str()),
-            domain: Some(caps.get(2).unwrap().as_str()),
-            top_level_domain: Some(caps.get(3).unwrap().as_str()),
-        });
+    /// Mixes out the bracketed ip v6 literal into a Domain structure, keeping the brackets.
+    fn domain_ipv6<'a>(&self, input: &'a str) -> Option<Domain<'a>> {
+        if !input.starts_with('[') {
+            return None;
+        }
+        Some(Domain {
+            subdomain: None,
+            domain: Some(input),
+            top_level_domain: None,
+        })
     }
 
-    /// Mixes out the ip v4 into a Domain structure.
+    /// Mixes out the ip v4 into a Domain structure. A host ending in a number is an address as a whole.
     fn domain_ipv4<'a>(&self, input: &'a str) -> Option<Domain<'a>> {
-        let re = Regex::new(r"([0-9]+)\.([0-9]+)\.([0-9]+)\.([0-9]+)").unwrap();
-        let caps = re.captures(input);
-        caps.as_ref()?;
-        return Some(Domain {
+        if !ends_in_a_number(input) {
+            return None;
+        }
+        Some(Domain {
             subdomain: None,
-            domain: Some(caps.unwrap().get(0).unwrap().as_str()),
+            domain: Some(input),
             top_level_domain: None,
-        });
+        })
     }
 
     /// Mixes out single-word alias (i.e.: "localhost") into a Domain structure.
     fn domain_alias<'a>(&self, input: &'a str) -> Option<Domain<'a>> {
-        let re = Regex::new(r".+").unwrap();
-        let caps = re.captures(input);
-        caps.as_ref()?;
-        return Some(Domain {
+        if input.is_empty() {
+            return None;
+        }
+        Some(Domain {
             subdomain: None,
-            domain: Some(caps.unwrap().get(0).unwrap().as_str()),
+            domain: Some(input),
             top_level_domain: None,
-        });
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::public_suffix::PublicSuffixList;
 
     #[test]
     fn test_domain_ipv4_when_typical() {
@@ -101,7 +127,7 @@ mod tests {
             domain: Some("192.168.178.242"),
             top_level_domain: None,
         };
-        let result = Parser::new(None).domain_ipv4(input).unwrap();
+        let result = Parser::new(None).domain(input);
 
         assert_eq!(result, expected);
     }
@@ -114,7 +140,39 @@ mod tests {
             domain: Some("1.2.3.4"),
             top_level_domain: None,
         };
-        let result = Parser::new(None).domain_ipv4(input).unwrap();
+        let result = Parser::new(None).domain(input);
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_domain_ipv4_is_none_when_address_followed_by_labels() {
+        let result = Parser::new(None).domain_ipv4("1.2.3.4.evil.com");
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_domain_ipv4_when_short_form() {
+        let input = "http://0x7f.1/";
+        let expected = Domain {
+            subdomain: None,
+            domain: Some("0x7f.1"),
+            top_level_domain: None,
+        };
+        let result = Parser::new(None).domain(input);
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_domain_works_when_ipv6() {
+        let input = "http://[2001:db8::1]:8080/x";
+        let expected = Domain {
+            subdomain: None,
+            domain: Some("[2001:db8::1]"),
+            top_level_domain: None,
+        };
+        let result = Parser::new(None).domain(input);
 
         assert_eq!(result, expected);
     }
@@ -148,8 +206,8 @@ mod tests {
         let input = "https://www.example.co.uk:443/blog/article/search?docid=720&hl=en#dayone";
         let expected = Domain {
             subdomain: Some("www"),
-            domain: Some("example.co"),
-            top_level_domain: Some("uk"),
+            domain: Some("example"),
+            top_level_domain: Some("co.uk"),
         };
         let result = Parser::new(None).domain(input);
         assert_eq!(result, expected);
@@ -160,7 +218,31 @@ mod tests {
         let input = "https://www.example.co.uk/blog/article/search?docid=720&hl=en#dayone";
         let expected = Domain {
             subdomain: Some("www"),
-            domain: Some("example.co"),
+            domain: Some("example"),
+            top_level_domain: Some("co.uk"),
+        };
+        let result = Parser::new(None).domain(input);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_domain_works_when_multi_level_subdomain() {
+        let input = "https://a.b.example.com/";
+        let expected = Domain {
+            subdomain: Some("a.b"),
+            domain: Some("example"),
+            top_level_domain: Some("com"),
+        };
+        let result = Parser::new(None).domain(input);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_domain_works_when_host_is_public_suffix() {
+        let input = "https://co.uk/";
+        let expected = Domain {
+            subdomain: None,
+            domain: Some("co"),
             top_level_domain: Some("uk"),
         };
         let result = Parser::new(None).domain(input);
@@ -168,10 +250,32 @@ mod tests {
     }
 
     #[test]
-    fn test_subdomain_domain_fails_when_garbage() {
+    fn test_domain_works_when_custom_public_suffix_list() {
+        let list = PublicSuffixList::parse("com\nexample.com");
+        let input = "https://www.shop.example.com/";
+        let expected = Domain {
+            subdomain: Some("www"),
+            domain: Some("shop"),
+            top_level_domain: Some("example.com"),
+        };
+        let result = Parser::new(None)
+            .with_public_suffix_list(list)
+            .domain(input);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_registrable_domain_is_none_when_ip() {
+        let parser = Parser::new(None);
+        assert!(parser.registrable_domain("http://192.168.0.1/").is_none());
+        assert!(parser.registrable_domain("http://[::1]/").is_none());
+    }
+
+    #[test]
+    fn test_domain_public_suffix_fails_when_garbage() {
         let input = "foobar";
         let expected = None;
-        let result = Parser::new(None).subdomain_domain(input);
+        let result = Parser::new(None).domain_public_suffix(input);
         assert_eq!(result, expected);
     }
 
@@ -179,7 +283,7 @@ mod tests {
     fn test_domain_ipv4_fails_when_garbage() {
         let input = "foobar";
         let expected = None;
-        let result = Parser::new(None).subdomain_domain(input);
+        let result = Parser::new(None).domain_ipv4(input);
         assert_eq!(result, expected);
     }
 
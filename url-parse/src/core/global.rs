@@ -1,14 +1,22 @@
-#[derive(Debug)]
+/// The parts of a domain, borrowed from the input. With the `serde` feature, it only deserializes without copying,
+/// e.g. with `serde_json::from_str()` from strings without escapes. Deserialize an `Url` to own the parts instead.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Domain<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub subdomain: Option<&'a str>,
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub domain: Option<&'a str>,
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub top_level_domain: Option<&'a str>,
 }
 
 impl<'a> PartialEq for Domain<'a> {
     /// Enables comparison between two domain objects.
     fn eq(&self, other: &Self) -> bool {
-        self.subdomain == other.subdomain && self.domain == other.domain && self.top_level_domain == other.top_level_domain
+        self.subdomain == other.subdomain
+            && self.domain == other.domain
+            && self.top_level_domain == other.top_level_domain
     }
 }
 
@@ -41,32 +49,64 @@ mod tests {
 
     #[test]
     fn test_domain_equals_when_typical() {
-        let left = Domain{ subdomain: Some("one"), domain: Some("two"), top_level_domain: Some("three")};
-        let right = Domain{ subdomain: Some("one"), domain: Some("two"), top_level_domain: Some("three")};
+        let left = Domain {
+            subdomain: Some("one"),
+            domain: Some("two"),
+            top_level_domain: Some("three"),
+        };
+        let right = Domain {
+            subdomain: Some("one"),
+            domain: Some("two"),
+            top_level_domain: Some("three"),
+        };
 
         assert_eq!(left, right);
     }
 
     #[test]
     fn test_domain_not_equals_when_diff_subdomain() {
-        let left = Domain{ subdomain: Some("one"), domain: Some("two"), top_level_domain: Some("three")};
-        let right = Domain{ subdomain: Some("X"), domain: Some("two"), top_level_domain: Some("three")};
+        let left = Domain {
+            subdomain: Some("one"),
+            domain: Some("two"),
+            top_level_domain: Some("three"),
+        };
+        let right = Domain {
+            subdomain: Some("X"),
+            domain: Some("two"),
+            top_level_domain: Some("three"),
+        };
 
         assert_ne!(left, right);
     }
 
     #[test]
     fn test_domain_not_equals_when_diff_domain() {
-        let left = Domain{ subdomain: Some("one"), domain: Some("two"), top_level_domain: Some("three")};
-        let right = Domain{ subdomain: Some("one"), domain: Some("X"), top_level_domain: Some("three")};
+        let left = Domain {
+            subdomain: Some("one"),
+            domain: Some("two"),
+            top_level_domain: Some("three"),
+        };
+        let right = Domain {
+            subdomain: Some("one"),
+            domain: Some("X"),
+            top_level_domain: Some("three"),
+        };
 
         assert_ne!(left, right);
     }
 
     #[test]
     fn test_domain_not_equals_when_diff_top_level_domain() {
-        let left = Domain{ subdomain: Some("one"), domain: Some("two"), top_level_domain: Some("three")};
-        let right = Domain{ subdomain: Some("one"), domain: Some("two"), top_level_domain: Some("X")};
+        let left = Domain {
+            subdomain: Some("one"),
+            domain: Some("two"),
+            top_level_domain: Some("three"),
+        };
+        let right = Domain {
+            subdomain: Some("one"),
+            domain: Some("two"),
+            top_level_domain: Some("X"),
+        };
 
         assert_ne!(left, right);
     }
@@ -0,0 +1,603 @@
+use crate::core::percent_encoding::{percent_decode, utf8_percent_encode, EncodeSet};
+use crate::core::tokenizer::Tokens;
+use crate::core::Parser;
+use crate::error::{ParseError, ParseErrorKind};
+use idna::AsciiDenyList;
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// The host of an url, classified by what it denotes.
+///
+/// Domains are normalized with UTS #46 IDNA processing: they are lowercased and internationalized labels are encoded
+/// with Punycode. Hosts of urls parsed in `ParseMode::Whatwg` are percent-decoded beforehand.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Host {
+    Domain(String),
+    Ipv4(Ipv4Addr),
+    Ipv6(Ipv6Addr),
+    Opaque(String),
+}
+
+impl Host {
+    /// Run the WHATWG host parser on `input`. Hosts of non-special schemes are parsed as opaque hosts.
+    /// Error positions are byte offsets into `input`.
+    pub(crate) fn parse(input: &str, is_opaque: bool) -> Result<Host, ParseError> {
+        if let Some(rest) = input.strip_prefix('[') {
+            return rest
+                .strip_suffix(']')
+                .and_then(parse_ipv6)
+                .map(Host::Ipv6)
+                .ok_or_else(|| ParseError::new(ParseErrorKind::InvalidIpv6, 0, input));
+        }
+        if is_opaque {
+            return parse_opaque(input);
+        }
+        let domain = String::from_utf8_lossy(&percent_decode(input.as_bytes())).into_owned();
+        let ascii_domain = domain_to_ascii(&domain).map_err(|e| locate(e, input))?;
+        if ends_in_a_number(&ascii_domain) {
+            return parse_ipv4(&ascii_domain)
+                .map(Host::Ipv4)
+                .ok_or_else(|| ParseError::new(ParseErrorKind::InvalidIpv4, 0, input));
+        }
+        Ok(Host::Domain(ascii_domain))
+    }
+
+    /// Classify a host as written in an url, without percent-decoding it. A host ending in a number must be an IPv4
+    /// address, a bracketed host an IPv6 literal and any other host a valid domain, otherwise there is no valid host.
+    pub(crate) fn classify(input: &str) -> Option<Host> {
+        if input.is_empty() {
+            return None;
+        }
+        if input.starts_with('[') {
+            return parse_ipv6_literal(input).map(|(address, _)| Host::Ipv6(address));
+        }
+        let ascii_domain = idna_to_ascii(input).ok()?;
+        if ends_in_a_number(&ascii_domain) {
+            return parse_ipv4(&ascii_domain).map(Host::Ipv4);
+        }
+        Some(Host::Domain(ascii_domain.into_owned()))
+    }
+
+    /// Classify an already serialized host, i.e. one read back from a `Url`.
+    pub(crate) fn from_serialized(input: &str) -> Host {
+        if let Some(address) = input
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+            .and_then(parse_ipv6)
+        {
+            return Host::Ipv6(address);
+        }
+        match input.parse::<Ipv4Addr>() {
+            Ok(address) => Host::Ipv4(address),
+            Err(_) => Host::Domain(input.to_string()),
+        }
+    }
+}
+
+impl Parser {
+    /// Extract the host from the url. Addresses are parsed following the WHATWG URL Standard, which accepts
+    /// IPv4 addresses in octal, hexadecimal and shortened forms.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::net::Ipv4Addr;
+    /// use url_parse::core::Parser;
+    /// use url_parse::core::host::Host;
+    /// let parser = Parser::new(None);
+    /// assert_eq!(parser.host("http://0x7f.1/"), Some(Host::Ipv4(Ipv4Addr::new(127, 0, 0, 1))));
+    /// assert_eq!(parser.host("https://www.example.com/"), Some(Host::Domain("www.example.com".to_string())));
+    /// ```
+    pub fn host(&self, input: &str) -> Option<Host> {
+        self.host_from_tokens(input, &self.tokenize(input))
+    }
+
+    pub(crate) fn host_from_tokens(&self, input: &str, tokens: &Tokens) -> Option<Host> {
+        Host::classify(&input[tokens.host.clone()])
+    }
+}
+
+/// Serialize the host the way it appears in an URL (IPv6 addresses are bracketed).
+impl std::fmt::Display for Host {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Host::Domain(v) | Host::Opaque(v) => write!(f, "{}", v),
+            Host::Ipv4(v) => write!(f, "{}", v),
+            Host::Ipv6(v) => write!(f, "[{}]", serialize_ipv6(v)),
+        }
+    }
+}
+
+/// Code points which may never appear in a host.
+fn is_forbidden_host_code_point(c: char) -> bool {
+    matches!(
+        c,
+        '\0' | '\t'
+            | '\n'
+            | '\r'
+            | ' '
+            | '#'
+            | '/'
+            | ':'
+            | '<'
+            | '>'
+            | '?'
+            | '@'
+            | '['
+            | '\\'
+            | ']'
+            | '^'
+            | '|'
+    )
+}
+
+/// Code points which may never appear in a domain, a superset of the forbidden host code points.
+fn is_forbidden_domain_code_point(c: char) -> bool {
+    is_forbidden_host_code_point(c) || c.is_ascii_control() || c == '%'
+}
+
+/// Point an error raised on the percent-decoded domain at the same snippet within the original `input`.
+fn locate(error: ParseError, input: &str) -> ParseError {
+    let position = input.find(error.snippet.as_str()).unwrap_or(0);
+    ParseError { position, ..error }
+}
+
+fn parse_opaque(input: &str) -> Result<Host, ParseError> {
+    if let Some((i, c)) = input
+        .char_indices()
+        .find(|&(_, c)| is_forbidden_host_code_point(c))
+    {
+        return Err(ParseError::new(
+            ParseErrorKind::InvalidHostCharacter,
+            i,
+            c.to_string(),
+        ));
+    }
+    Ok(Host::Opaque(utf8_percent_encode(
+        input,
+        EncodeSet::C0Control,
+    )))
+}
+
+/// Convert a domain to ASCII and reject forbidden domain code points, as the WHATWG host parser does.
+fn domain_to_ascii(domain: &str) -> Result<String, ParseError> {
+    let result = idna_to_ascii(domain)?;
+    if result.is_empty() {
+        return Err(ParseError::new(ParseErrorKind::EmptyHost, 0, ""));
+    }
+    if let Some((i, c)) = result
+        .char_indices()
+        .find(|&(_, c)| is_forbidden_domain_code_point(c))
+    {
+        return Err(ParseError::new(
+            ParseErrorKind::InvalidHostCharacter,
+            i,
+            c.to_string(),
+        ));
+    }
+    Ok(result.into_owned())
+}
+
+/// Run UTS #46 processing on a domain, mapping it to lowercase and encoding internationalized labels with Punycode.
+/// Domains which are already normalized are borrowed.
+pub(crate) fn idna_to_ascii(domain: &str) -> Result<Cow<'_, str>, ParseError> {
+    idna::domain_to_ascii_cow(domain.as_bytes(), AsciiDenyList::EMPTY)
+        .map_err(|_| ParseError::new(ParseErrorKind::InvalidDomain, 0, domain))
+}
+
+/// Check whether the last label of a domain looks like a number, in which case the whole domain must be an IPv4 address.
+pub(crate) fn ends_in_a_number(input: &str) -> bool {
+    let mut parts: Vec<&str> = input.split('.').collect();
+    if parts.last() == Some(&"") {
+        if parts.len() == 1 {
+            return false;
+        }
+        parts.pop();
+    }
+    let last = parts.last().copied().unwrap_or_default();
+    if !last.is_empty() && last.bytes().all(|b| b.is_ascii_digit()) {
+        return true;
+    }
+    parse_ipv4_number(last).is_some()
+}
+
+/// Parse a single IPv4 part, which may be written in decimal, octal (`0` prefix) or hexadecimal (`0x` prefix).
+fn parse_ipv4_number(input: &str) -> Option<u64> {
+    if input.is_empty() {
+        return None;
+    }
+    let (digits, radix) = if input.starts_with("0x") || input.starts_with("0X") {
+        (&input[2..], 16)
+    } else if input.len() >= 2 && input.starts_with('0') {
+        (&input[1..], 8)
+    } else {
+        (input, 10)
+    };
+    if digits.is_empty() {
+        return Some(0);
+    }
+    let mut value: u64 = 0;
+    for c in digits.chars() {
+        let digit = c.to_digit(radix)?;
+        value = value
+            .saturating_mul(radix as u64)
+            .saturating_add(digit as u64);
+    }
+    Some(value)
+}
+
+pub(crate) fn parse_ipv4(input: &str) -> Option<Ipv4Addr> {
+    let mut parts: Vec<&str> = input.split('.').collect();
+    if parts.last() == Some(&"") && parts.len() > 1 {
+        parts.pop();
+    }
+    if parts.len() > 4 {
+        return None;
+    }
+    let numbers = parts
+        .iter()
+        .map(|part| parse_ipv4_number(part))
+        .collect::<Option<Vec<u64>>>()?;
+    let (last, leading) = numbers.split_last()?;
+    if leading.iter().any(|&n| n > 255) || *last >= 256u64.pow(5 - numbers.len() as u32) {
+        return None;
+    }
+    let mut address = *last;
+    for (i, n) in leading.iter().enumerate() {
+        address += n * 256u64.pow(3 - i as u32);
+    }
+    Some(Ipv4Addr::from(address as u32))
+}
+
+fn parse_ipv6(input: &str) -> Option<Ipv6Addr> {
+    let input: Vec<char> = input.chars().collect();
+    let at = |i: usize| input.get(i).copied();
+    let mut address = [0u16; 8];
+    let mut piece_index = 0;
+    let mut compress: Option<usize> = None;
+    let mut pointer = 0;
+
+    if at(pointer) == Some(':') {
+        if at(pointer + 1) != Some(':') {
+            return None;
+        }
+        pointer += 2;
+        piece_index += 1;
+        compress = Some(piece_index);
+    }
+    while at(pointer).is_some() {
+        if piece_index == 8 {
+            return None;
+        }
+        if at(pointer) == Some(':') {
+            if compress.is_some() {
+                return None;
+            }
+            pointer += 1;
+            piece_index += 1;
+            compress = Some(piece_index);
+            continue;
+        }
+        let mut value: u16 = 0;
+        let mut length = 0;
+        while length < 4 {
+            match at(pointer).and_then(|c| c.to_digit(16)) {
+                Some(digit) => value = value * 0x10 + digit as u16,
+                None => break,
+            }
+            pointer += 1;
+            length += 1;
+        }
+        match at(pointer) {
+            Some('.') => {
+                if length == 0 || piece_index > 6 {
+                    return None;
+                }
+                pointer -= length;
+                let mut numbers_seen = 0;
+                while at(pointer).is_some() {
+                    if numbers_seen > 0 {
+                        if at(pointer) == Some('.') && numbers_seen < 4 {
+                            pointer += 1;
+                        } else {
+                            return None;
+                        }
+                    }
+                    let mut ipv4_piece: Option<u16> = None;
+                    while let Some(digit) = at(pointer).and_then(|c| c.to_digit(10)) {
+                        ipv4_piece = match ipv4_piece {
+                            None => Some(digit as u16),
+                            Some(0) => return None,
+                            Some(v) => Some(v * 10 + digit as u16),
+                        };
+                        if ipv4_piece > Some(255) {
+                            return None;
+                        }
+                        pointer += 1;
+                    }
+                    let ipv4_piece = ipv4_piece?;
+                    address[piece_index] = address[piece_index] * 0x100 + ipv4_piece;
+                    numbers_seen += 1;
+                    if numbers_seen == 2 || numbers_seen == 4 {
+                        piece_index += 1;
+                    }
+                }
+                if numbers_seen != 4 {
+                    return None;
+                }
+                break;
+            }
+            Some(':') => {
+                pointer += 1;
+                at(pointer)?;
+            }
+            Some(_) => return None,
+            None => {}
+        }
+        address[piece_index] = value;
+        piece_index += 1;
+    }
+    match compress {
+        Some(compress) => {
+            let mut swaps = piece_index - compress;
+            piece_index = 7;
+            while piece_index != 0 && swaps > 0 {
+                address.swap(piece_index, compress + swaps - 1);
+                piece_index -= 1;
+                swaps -= 1;
+            }
+        }
+        None if piece_index != 8 => return None,
+        None => {}
+    }
+    Some(Ipv6Addr::from(address))
+}
+
+/// Parse a bracketed IPv6 literal as written in an url, returning the address and the zone identifier if present.
+/// As specified by RFC 6874, the zone identifier follows the address, separated by a percent-encoded `%` (`%25`).
+pub(crate) fn parse_ipv6_literal(input: &str) -> Option<(Ipv6Addr, Option<&str>)> {
+    let inner = input.strip_prefix('[')?.strip_suffix(']')?;
+    let (address, zone_id) = match inner.split_once("%25") {
+        Some((address, zone_id)) => (address, Some(zone_id)),
+        None => (inner, None),
+    };
+    let is_valid_zone_id = zone_id.is_none_or(|zone_id| {
+        !zone_id.is_empty()
+            && zone_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~' | '%'))
+    });
+    if !is_valid_zone_id {
+        return None;
+    }
+    Some((parse_ipv6(address)?, zone_id))
+}
+
+/// Serialize an IPv6 address, compressing the first longest run of zero pieces.
+/// Unlike `Ipv6Addr`'s `Display`, embedded IPv4 addresses are written in hexadecimal.
+fn serialize_ipv6(address: &Ipv6Addr) -> String {
+    let pieces = address.segments();
+    let mut compress: Option<(usize, usize)> = None;
+    let mut i = 0;
+    while i < pieces.len() {
+        let start = i;
+        while i < pieces.len() && pieces[i] == 0 {
+            i += 1;
+        }
+        let length = i - start;
+        if length > 1 && compress.is_none_or(|(_, longest)| length > longest) {
+            compress = Some((start, length));
+        }
+        i += 1;
+    }
+
+    let mut result = String::new();
+    let mut i = 0;
+    while i < pieces.len() {
+        if let Some((start, length)) = compress.filter(|&(start, _)| start == i) {
+            result += if start == 0 { "::" } else { ":" };
+            i += length;
+            continue;
+        }
+        let _ = write!(result, "{:x}", pieces[i]);
+        if i != 7 {
+            result += ":";
+        }
+        i += 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_parse_works_when_domain() {
+        let result = Host::parse("WWW.Example.com", false).unwrap();
+        assert_eq!(result, Host::Domain("www.example.com".to_string()));
+    }
+
+    #[test]
+    fn test_host_parse_works_when_percent_encoded_domain() {
+        let result = Host::parse("%65xample.com", false).unwrap();
+        assert_eq!(result, Host::Domain("example.com".to_string()));
+    }
+
+    #[test]
+    fn test_host_parse_fails_when_forbidden_code_point() {
+        let result = Host::parse("exa mple.com", false);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_host_parse_reports_position_when_forbidden_code_point() {
+        let result = Host::parse("exa mple.com", false);
+        assert_eq!(
+            result,
+            Err(ParseError::new(
+                ParseErrorKind::InvalidHostCharacter,
+                3,
+                " "
+            ))
+        );
+    }
+
+    #[test]
+    fn test_host_parse_works_when_opaque() {
+        let result = Host::parse("EXAMPLE.com", true).unwrap();
+        assert_eq!(result, Host::Opaque("EXAMPLE.com".to_string()));
+    }
+
+    #[test]
+    fn test_host_parse_works_when_ipv4_short_forms() {
+        let expected = Host::Ipv4(Ipv4Addr::new(192, 168, 0, 1));
+        for input in [
+            "192.168.0.1",
+            "0xc0.0250.1",
+            "192.168.1",
+            "3232235521",
+            "0xC0A80001.",
+        ] {
+            assert_eq!(Host::parse(input, false).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_host_parse_fails_when_ipv4_out_of_range() {
+        for input in [
+            "256.0.0.1",
+            "1.2.3.256",
+            "4294967296",
+            "1.2.3.4.5",
+            "1.2.3.09",
+        ] {
+            assert!(Host::parse(input, false).is_err());
+        }
+    }
+
+    #[test]
+    fn test_host_parse_works_when_ipv6() {
+        let result = Host::parse("[2001:DB8::1]", false).unwrap();
+        assert_eq!(result.to_string(), "[2001:db8::1]");
+    }
+
+    #[test]
+    fn test_host_parse_works_when_ipv6_embedded_ipv4() {
+        let result = Host::parse("[::ffff:192.168.0.1]", false).unwrap();
+        assert_eq!(result.to_string(), "[::ffff:c0a8:1]");
+    }
+
+    #[test]
+    fn test_classify_works_when_typical() {
+        assert_eq!(
+            Host::classify("WWW.Example.com"),
+            Some(Host::Domain("www.example.com".to_string()))
+        );
+        assert_eq!(
+            Host::classify("1.2.3.4.evil.com"),
+            Some(Host::Domain("1.2.3.4.evil.com".to_string()))
+        );
+        assert_eq!(
+            Host::classify("0300.0250.0.1"),
+            Some(Host::Ipv4(Ipv4Addr::new(192, 168, 0, 1)))
+        );
+        assert_eq!(
+            Host::classify("[::1]"),
+            Some(Host::Ipv6(Ipv6Addr::LOCALHOST))
+        );
+    }
+
+    #[test]
+    fn test_classify_is_none_when_invalid() {
+        assert!(Host::classify("").is_none());
+        assert!(Host::classify("999.1.1.1").is_none());
+        assert!(Host::classify("[1:2]").is_none());
+    }
+
+    #[test]
+    fn test_host_works_when_url() {
+        let parser = Parser::new(None);
+        assert_eq!(
+            parser.host("https://user@192.168.0.1:443/"),
+            Some(Host::Ipv4(Ipv4Addr::new(192, 168, 0, 1)))
+        );
+        assert_eq!(
+            parser.host("http://[2001:db8::1]:8080/x"),
+            Some(Host::Ipv6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)))
+        );
+        assert_eq!(parser.host("file:///etc/hosts"), None);
+    }
+
+    #[test]
+    fn test_classify_works_when_internationalized() {
+        let expected = Some(Host::Domain("xn--mnchen-3ya.de".to_string()));
+        assert_eq!(Host::classify("münchen.de"), expected);
+        assert_eq!(Host::classify("MÜNCHEN.de"), expected);
+        assert_eq!(Host::classify("xn--mnchen-3ya.de"), expected);
+    }
+
+    #[test]
+    fn test_classify_is_none_when_idna_fails() {
+        assert!(Host::classify("xn--a.de").is_none());
+    }
+
+    #[test]
+    fn test_host_parse_works_when_internationalized() {
+        let result = Host::parse("Ｇｏ.com", false).unwrap();
+        assert_eq!(result, Host::Domain("go.com".to_string()));
+    }
+
+    #[test]
+    fn test_parse_ipv6_literal_works_when_zone_id() {
+        let result = parse_ipv6_literal("[fe80::1%25eth0]").unwrap();
+        assert_eq!(
+            result,
+            (Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), Some("eth0"))
+        );
+    }
+
+    #[test]
+    fn test_parse_ipv6_literal_fails_when_invalid_zone_id() {
+        assert!(parse_ipv6_literal("[fe80::1%25]").is_none());
+        assert!(parse_ipv6_literal("[fe80::1%25eth/0]").is_none());
+        assert!(parse_ipv6_literal("[fe80::1%eth0]").is_none());
+    }
+
+    #[test]
+    fn test_host_parse_fails_when_ipv6_invalid() {
+        for input in [
+            "[1:2]",
+            "[::1::]",
+            "[:1]",
+            "[1:2:3:4:5:6:7:8:9]",
+            "[::1.2.3]",
+            "[::1",
+        ] {
+            assert!(Host::parse(input, false).is_err());
+        }
+    }
+
+    #[test]
+    fn test_serialize_ipv6_compresses_first_longest_run() {
+        let input = Ipv6Addr::new(1, 0, 0, 2, 0, 0, 0, 3);
+        assert_eq!(serialize_ipv6(&input), "1:0:0:2::3");
+    }
+
+    #[test]
+    fn test_from_serialized_works_when_typical() {
+        assert_eq!(
+            Host::from_serialized("127.0.0.1"),
+            Host::Ipv4(Ipv4Addr::LOCALHOST)
+        );
+        assert_eq!(
+            Host::from_serialized("[::1]"),
+            Host::Ipv6(Ipv6Addr::LOCALHOST)
+        );
+        assert_eq!(
+            Host::from_serialized("localhost"),
+            Host::Domain("localhost".to_string())
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use crate::core::host::Host;
+use crate::core::join::remove_dot_segments;
+use crate::core::Parser;
+use crate::url::Url;
+
+/// What `Parser::normalize` does with a `/` at the end of the path.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TrailingSlash {
+    /// Leave the path as it is.
+    #[default]
+    Keep,
+    /// Append a `/` unless the path already ends with one, so that `/docs` becomes `/docs/`.
+    Add,
+    /// Remove the `/` at the end of the path, unless the path is just `/`.
+    Remove,
+}
+
+/// The optional steps of `Parser::normalize`. The steps of RFC 3986 §6.2.2 are always applied, since they never change
+/// which resource an url refers to; sorting the query and changing the trailing slash may, and are disabled by default.
+///
+/// # Example
+/// ```rust
+/// use url_parse::core::normalize::{Normalization, TrailingSlash};
+/// let options = Normalization::default()
+///     .with_sorted_query(true)
+///     .with_trailing_slash(TrailingSlash::Remove);
+/// assert!(options.sorted_query);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Normalization {
+    /// Sort the `&`-separated pairs of the query by name, keeping pairs with the same name in order.
+    pub sorted_query: bool,
+    pub trailing_slash: TrailingSlash,
+}
+
+impl Normalization {
+    pub fn with_sorted_query(mut self, sorted_query: bool) -> Self {
+        self.sorted_query = sorted_query;
+        self
+    }
+
+    pub fn with_trailing_slash(mut self, trailing_slash: TrailingSlash) -> Self {
+        self.trailing_slash = trailing_slash;
+        self
+    }
+}
+
+impl Parser {
+    /// Normalize an url following RFC 3986 §6: the scheme and host are lowercased, the default port of the scheme
+    /// according to the schemes known to this parser is dropped, hexadecimal digits of percent-encoded bytes are
+    /// uppercased, escapes of unreserved characters are decoded and dot segments are removed from the path. An empty
+    /// path becomes `/` when the url has a host.
+    ///
+    /// # Example
+    /// ```rust
+    /// use url_parse::core::Parser;
+    /// use url_parse::core::normalize::Normalization;
+    /// let parser = Parser::new(None);
+    /// let url = parser.parse("HTTP://User@Example.COM:80/a/./b/../%7Ec%2f?q=%3a").unwrap();
+    /// let result = parser.normalize(&url, Normalization::default());
+    /// assert_eq!(result.serialize(), "http://User@example.com/a/~c%2F?q=%3A");
+    /// ```
+    pub fn normalize(&self, url: &Url, options: Normalization) -> Url {
+        let scheme = url.scheme.as_ref().map(|scheme| scheme.to_lowercase());
+        let default_port = scheme
+            .as_deref()
+            .and_then(|scheme| self.default_port(scheme));
+        let lowercase = |label: &Option<String>| label.as_ref().map(|label| label.to_lowercase());
+        let host = url.host.as_ref().map(|host| match host {
+            Host::Domain(domain) => Host::Domain(domain.to_lowercase()),
+            Host::Opaque(host) => Host::Opaque(normalize_escapes(&host.to_lowercase())),
+            host => host.clone(),
+        });
+        let escapes = |text: &Option<String>| text.as_deref().map(normalize_escapes);
+
+        let mut query = escapes(&url.query);
+        if options.sorted_query {
+            query = query.map(|query| sort_query(&query));
+        }
+
+        Url {
+            scheme,
+            scheme_separator: url.scheme_separator,
+            user_pass: (escapes(&url.user_pass.0), escapes(&url.user_pass.1)),
+            subdomain: lowercase(&url.subdomain),
+            domain: lowercase(&url.domain),
+            top_level_domain: lowercase(&url.top_level_domain),
+            host,
+            port: url.port.filter(|port| Some(*port) != default_port),
+            default_port,
+            path: match &url.path {
+                Some(path) => Some(normalize_path(path, options.trailing_slash)),
+                None if url.host.is_some() => Some(vec![String::new()]),
+                None => None,
+            },
+            query,
+            anchor: escapes(&url.anchor),
+            opaque_path: escapes(&url.opaque_path),
+            scp_like: url.scp_like,
+        }
+    }
+
+    /// Check whether two urls are equivalent, i.e. equal after being normalized with the same options.
+    ///
+    /// # Example
+    /// ```rust
+    /// use url_parse::core::Parser;
+    /// use url_parse::core::normalize::Normalization;
+    /// let parser = Parser::new(None);
+    /// let a = parser.parse("HTTP://Example.COM:80/a/./b/../c").unwrap();
+    /// let b = parser.parse("http://example.com/a/c").unwrap();
+    /// assert_ne!(a, b);
+    /// assert!(parser.equivalent(&a, &b, Normalization::default()));
+    /// ```
+    pub fn equivalent(&self, a: &Url, b: &Url, options: Normalization) -> bool {
+        self.normalize(a, options) == self.normalize(b, options)
+    }
+}
+
+/// Uppercase the hexadecimal digits of `%XX` escapes and decode those of unreserved characters (RFC 3986 §6.2.2.2).
+fn normalize_escapes(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|hex| bytes[i] == b'%' && hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(byte)
+                if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') =>
+            {
+                result.push(byte);
+                i += 3;
+            }
+            Some(byte) => {
+                result.extend(format!("%{:02X}", byte).bytes());
+                i += 3;
+            }
+            None => {
+                result.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(result).unwrap_or_else(|_| input.to_string())
+}
+
+/// Normalize the escapes of each segment, then remove dot segments and apply the trailing slash option.
+fn normalize_path(path: &[String], trailing_slash: TrailingSlash) -> Vec<String> {
+    let joined: Vec<String> = path
+        .iter()
+        .map(|segment| normalize_escapes(segment))
+        .collect();
+    let absolute = format!("/{}", joined.join("/"));
+    let mut result: Vec<String> = remove_dot_segments(&absolute)[1..]
+        .split('/')
+        .map(str::to_string)
+        .collect();
+    let ends_with_slash = result.last().is_some_and(String::is_empty);
+    match trailing_slash {
+        TrailingSlash::Add if !ends_with_slash => result.push(String::new()),
+        TrailingSlash::Remove if ends_with_slash && result.len() > 1 => {
+            result.pop();
+        }
+        _ => {}
+    }
+    result
+}
+
+/// Stable sort of the `&`-separated sequences of a query by their name, keeping their encoding.
+fn sort_query(query: &str) -> String {
+    let mut pairs: Vec<&str> = query.split('&').collect();
+    pairs.sort_by_key(|pair| pair.split('=').next().unwrap_or_default());
+    pairs.join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_escapes_works_when_typical() {
+        let result = normalize_escapes("%7euser%2fname%c3%A9%zz%4");
+        assert_eq!(result, "~user%2Fname%C3%A9%zz%4");
+    }
+
+    #[test]
+    fn test_normalize_path_works_when_dot_segments() {
+        let path: Vec<String> = ["a", ".", "b", "..", "%2E%2E", "c", ".."]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let result = normalize_path(&path, TrailingSlash::Keep);
+        assert_eq!(result, vec![""]);
+    }
+
+    #[test]
+    fn test_normalize_path_works_when_trailing_slash() {
+        let path = vec!["docs".to_string()];
+        let added = normalize_path(&path, TrailingSlash::Add);
+        let removed = normalize_path(&added, TrailingSlash::Remove);
+        let root = normalize_path(&[String::new()], TrailingSlash::Remove);
+        assert_eq!(added, vec!["docs", ""]);
+        assert_eq!(removed, vec!["docs"]);
+        assert_eq!(root, vec![""]);
+    }
+
+    #[test]
+    fn test_sort_query_works_when_duplicate_names() {
+        let result = sort_query("b=2&a=1&b=1&a");
+        assert_eq!(result, "a=1&a&b=2&b=1");
+    }
+
+    #[test]
+    fn test_normalize_works_when_custom_port_mapping() {
+        let mut mappings = std::collections::HashMap::new();
+        mappings.insert("myschema", (8888, "My custom schema"));
+        let parser = Parser::new(Some(mappings));
+        let url = parser.parse("myschema://example.com:8888/a").unwrap();
+        let result = parser.normalize(&url, Normalization::default());
+        assert_eq!(result.port, None);
+        assert_eq!(result.serialize(), "myschema://example.com/a");
+    }
+
+    #[test]
+    fn test_normalize_keeps_other_port() {
+        let parser = Parser::new(None);
+        let url = parser.parse("https://example.com:8443/").unwrap();
+        let result = parser.normalize(&url, Normalization::default());
+        assert_eq!(result.port, Some(8443));
+    }
+
+    #[test]
+    fn test_equivalent_works_when_options() {
+        let parser = Parser::new(None);
+        let a = parser.parse("https://example.com/docs?b=2&a=1").unwrap();
+        let b = parser.parse("https://EXAMPLE.com/docs/?a=1&b=2").unwrap();
+        let options = Normalization::default()
+            .with_sorted_query(true)
+            .with_trailing_slash(TrailingSlash::Add);
+        assert!(!parser.equivalent(&a, &b, Normalization::default()));
+        assert!(parser.equivalent(&a, &b, options));
+    }
+
+    #[test]
+    fn test_equivalent_works_when_empty_path() {
+        let parser = Parser::new(None);
+        let a = parser.parse("http://example.com").unwrap();
+        let b = parser.parse("http://example.com/").unwrap();
+        let mut c = b.clone();
+        c.path = None;
+        let mut d = b.clone();
+        d.path = Some(Vec::new());
+        assert!(parser.equivalent(&a, &b, Normalization::default()));
+        assert!(parser.equivalent(&b, &c, Normalization::default()));
+        assert!(parser.equivalent(&b, &d, Normalization::default()));
+    }
+
+    #[test]
+    fn test_normalize_keeps_path_none_when_no_host() {
+        let parser = Parser::new(None);
+        let url = parser.parse("mailto:user@example.com").unwrap();
+        let result = parser.normalize(&url, Normalization::default());
+        assert_eq!(result.path, None);
+    }
+
+    #[test]
+    fn test_normalize_works_when_whatwg() {
+        use crate::core::parse_mode::ParseMode;
+        let parser = Parser::new(None).with_mode(ParseMode::Whatwg);
+        let url = parser.parse("http://example.com/%7efoo").unwrap();
+        let result = parser.normalize(&url, Normalization::default());
+        assert_eq!(result.serialize(), "http://example.com/~foo");
+    }
+}
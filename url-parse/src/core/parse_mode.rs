@@ -0,0 +1,23 @@
+/// Selects the algorithm `Parser::parse` uses.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Lenient, per-component heuristics. Accepts scheme-less input such as `google.com` and fills in default ports.
+    #[default]
+    Legacy,
+    /// The WHATWG URL Standard state machine, as implemented by web browsers.
+    Whatwg,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mode_default_is_legacy() {
+        let expected = ParseMode::Legacy;
+
+        let result = ParseMode::default();
+
+        assert_eq!(result, expected);
+    }
+}
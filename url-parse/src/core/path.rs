@@ -1,5 +1,5 @@
+use crate::core::tokenizer::Tokens;
 use crate::core::Parser;
-use crate::utils::Utils;
 
 impl Parser {
     /// Extract the path as a vector from the url.
@@ -13,18 +13,17 @@ impl Parser {
     /// assert_eq!(result, expected);
     /// ```
     pub fn path<'a>(&self, input: &'a str) -> Option<Vec<&'a str>> {
-        let input = Utils::substring_from_path_begin(self, input).unwrap_or("");
-        let input = Utils::substring_after_port(self, input);
-        let input = match input.chars().next() {
-            Some('/') => &input[1..],
-            _ => input,
-        };
-        let position_questionmark = input.find('?');
-        let path_string = match position_questionmark {
-            Some(v) => &input[..v],
-            None => input,
-        };
-        return Some(path_string.split('/').collect());
+        self.path_from_tokens(input, &self.tokenize(input))
+    }
+
+    pub(crate) fn path_from_tokens<'a>(
+        &self,
+        input: &'a str,
+        tokens: &Tokens,
+    ) -> Option<Vec<&'a str>> {
+        let path = tokens.path.clone().map_or("", |r| &input[r]);
+        let path = path.strip_prefix('/').unwrap_or(path);
+        Some(path.split('/').collect())
     }
 }
 
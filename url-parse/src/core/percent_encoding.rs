@@ -0,0 +1,159 @@
+use std::borrow::Cow;
+
+/// The percent-encode sets defined by the WHATWG URL Standard, each a superset of the previous one
+/// except for `SpecialQuery` and `Fragment`, which branch off `Query` and `C0Control` respectively.
+/// `PathSegment` additionally escapes `/`, for a single segment of the path.
+///
+/// None of the sets contains `%`, so encoding text which is already percent-encoded leaves it unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeSet {
+    /// Control characters and non-ASCII, used for opaque paths and hosts.
+    C0Control,
+    Fragment,
+    Query,
+    /// The query of urls with a special scheme, such as `http`, which also escapes `'`.
+    SpecialQuery,
+    Path,
+    PathSegment,
+    Userinfo,
+}
+
+impl EncodeSet {
+    /// Check whether an ASCII byte must be escaped in this set. Non-ASCII bytes are always escaped.
+    pub fn contains(self, byte: u8) -> bool {
+        if !(0x20..=0x7E).contains(&byte) {
+            return true;
+        }
+        match self {
+            EncodeSet::C0Control => false,
+            EncodeSet::Fragment => matches!(byte, b' ' | b'"' | b'<' | b'>' | b'`'),
+            EncodeSet::Query => matches!(byte, b' ' | b'"' | b'#' | b'<' | b'>'),
+            EncodeSet::SpecialQuery => byte == b'\'' || EncodeSet::Query.contains(byte),
+            EncodeSet::Path => {
+                matches!(byte, b'?' | b'`' | b'{' | b'}') || EncodeSet::Query.contains(byte)
+            }
+            EncodeSet::PathSegment => byte == b'/' || EncodeSet::Path.contains(byte),
+            EncodeSet::Userinfo => {
+                matches!(
+                    byte,
+                    b'/' | b':' | b';' | b'=' | b'@' | b'[' | b'\\' | b']' | b'^' | b'|'
+                ) || EncodeSet::Path.contains(byte)
+            }
+        }
+    }
+}
+
+/// Percent-encode the UTF-8 representation of `input`, escaping every byte contained in `set`.
+///
+/// # Example
+/// ```rust
+/// use url_parse::core::percent_encoding::{utf8_percent_encode, EncodeSet};
+/// assert_eq!(utf8_percent_encode("p@ss:wörd", EncodeSet::Userinfo), "p%40ss%3Aw%C3%B6rd");
+/// assert_eq!(utf8_percent_encode("a/b c", EncodeSet::PathSegment), "a%2Fb%20c");
+/// ```
+pub fn utf8_percent_encode(input: &str, set: EncodeSet) -> String {
+    let mut result = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        if set.contains(byte) {
+            result += &format!("%{:02X}", byte);
+        } else {
+            result.push(byte as char);
+        }
+    }
+    result
+}
+
+/// Replace every `%XX` escape with the byte it denotes. Malformed escapes are kept verbatim.
+pub fn percent_decode(input: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(input.len());
+    let mut i = 0;
+    while i < input.len() {
+        if input[i] == b'%' && i + 2 < input.len() {
+            if let (Some(high), Some(low)) = (hex_value(input[i + 1]), hex_value(input[i + 2])) {
+                result.push(high * 16 + low);
+                i += 3;
+                continue;
+            }
+        }
+        result.push(input[i]);
+        i += 1;
+    }
+    result
+}
+
+/// Percent-decode `input` into a string, replacing invalid UTF-8 with `U+FFFD`. Text without escapes is borrowed.
+///
+/// # Example
+/// ```rust
+/// use url_parse::core::percent_encoding::percent_decode_str;
+/// assert_eq!(percent_decode_str("p%40ss%3Aw%C3%B6rd"), "p@ss:wörd");
+/// ```
+pub fn percent_decode_str(input: &str) -> Cow<'_, str> {
+    if !input.contains('%') {
+        return Cow::Borrowed(input);
+    }
+    Cow::Owned(String::from_utf8_lossy(&percent_decode(input.as_bytes())).into_owned())
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    (byte as char).to_digit(16).map(|v| v as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utf8_percent_encode_works_when_typical() {
+        let result = utf8_percent_encode("a b", EncodeSet::Path);
+        assert_eq!(result, "a%20b");
+    }
+
+    #[test]
+    fn test_utf8_percent_encode_works_when_non_ascii() {
+        let result = utf8_percent_encode("ü", EncodeSet::C0Control);
+        assert_eq!(result, "%C3%BC");
+    }
+
+    #[test]
+    fn test_utf8_percent_encode_works_when_userinfo_delimiters() {
+        let result = utf8_percent_encode("a@b:c", EncodeSet::Userinfo);
+        assert_eq!(result, "a%40b%3Ac");
+    }
+
+    #[test]
+    fn test_utf8_percent_encode_works_when_path_segment() {
+        let result = utf8_percent_encode("a/b?c", EncodeSet::PathSegment);
+        assert_eq!(result, "a%2Fb%3Fc");
+    }
+
+    #[test]
+    fn test_utf8_percent_encode_keeps_escapes() {
+        let result = utf8_percent_encode("a%20b", EncodeSet::Userinfo);
+        assert_eq!(result, "a%20b");
+    }
+
+    #[test]
+    fn test_percent_decode_str_works_when_invalid_utf8() {
+        let result = percent_decode_str("a%FFb");
+        assert_eq!(result, "a\u{FFFD}b");
+    }
+
+    #[test]
+    fn test_percent_decode_str_borrows_when_no_escapes() {
+        let result = percent_decode_str("abc");
+        assert!(matches!(result, Cow::Borrowed("abc")));
+    }
+
+    #[test]
+    fn test_percent_decode_works_when_typical() {
+        let result = percent_decode(b"a%20b%c3%BC");
+        assert_eq!(result, "a bü".as_bytes());
+    }
+
+    #[test]
+    fn test_percent_decode_keeps_malformed_escapes() {
+        let result = percent_decode(b"%zz%4");
+        assert_eq!(result, b"%zz%4");
+    }
+}
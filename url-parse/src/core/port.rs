@@ -1,7 +1,6 @@
-use regex::Regex;
-
+use crate::core::tokenizer::Tokens;
 use crate::core::Parser;
-use crate::utils::Utils;
+use crate::error::{report, ParseError, ParseErrorKind};
 
 impl Parser {
     /// Extract the port from the url. If no port is present, it will be deduced from the scheme.
@@ -14,40 +13,50 @@ impl Parser {
     /// let port = Parser::new(None).port(input);
     /// assert_eq!(port.unwrap(), 443);
     /// ```
-    pub fn port(&self, input: &str) -> Option<u32> {
-        let rest = Utils::substring_after_login(self, input);
-        let position_colon = rest.find(':');
-    
-        if let Some(v) = position_colon {
-            let after = &rest[v + 1..];
-            let re = Regex::new(r"^[0-9]+").ok()?; // Cria regex e lida com erro
-            let caps = re.captures(after)?;
-    
-            // Tenta obter a captura e converter para u64
-            if let Some(port_str) = caps.get(0) {
-                // Tenta converter para u64 primeiro
-                if let Ok(port) = port_str.as_str().trim().parse::<u64>() {
-                    // Verifica se o valor está dentro do limite de u32
-                    if port <= u32::MAX as u64 {
-                        return Some(port as u32); // Converte para u32
-                    } else {
-                        println!("Número excede o limite permitido para u32.");
-                        return None; // Retorna None em caso de overflow
-                    }
-                } else {
-                    println!("Erro ao analisar a porta: não é um número válido.");
-                    return None; // Retorna None em caso de erro na conversão
-                }
-            }
+    ///
+    /// A port which is not a number or exceeds 65535 yields `None`; `parse()` reports it as `ParseErrorKind::InvalidPort`.
+    /// Use `Url::port` and `Url::port_or_known_default` to tell an explicit port from a deduced one.
+    pub fn port(&self, input: &str) -> Option<u16> {
+        let tokens = self.tokenize(input);
+        if tokens.port.is_some() {
+            return self
+                .explicit_port_from_tokens(input, &tokens)
+                .unwrap_or_else(|error| {
+                    report(&error);
+                    None
+                });
         }
-    
-        // Obtém a porta padrão com segurança
-        match self.scheme(input) {
-            Some((v, _)) => {
-                self.port_mappings.get(&v).map(|&(port, _)| port) // Usando map para evitar unwrap
-            }
-            None => None,
+        // A colon which does not introduce a port leaves the port undetermined instead of deducing it from the scheme.
+        if input[tokens.host.end..].contains(':') {
+            return None;
         }
+        self.default_port_from_tokens(input, &tokens)
+    }
+
+    /// The port written in the url, if any, failing when it exceeds 65535.
+    pub(crate) fn explicit_port_from_tokens(
+        &self,
+        input: &str,
+        tokens: &Tokens,
+    ) -> Result<Option<u16>, ParseError> {
+        let Some(digits) = tokens.port_digits(input) else {
+            return Ok(None);
+        };
+        input[digits.clone()].parse().map(Some).map_err(|_| {
+            let port = tokens.port.clone().unwrap_or(digits);
+            ParseError::new(ParseErrorKind::InvalidPort, port.start, &input[port])
+        })
+    }
+
+    /// The default port of the url's scheme according to the schemes known to this parser.
+    pub(crate) fn default_port_from_tokens(&self, input: &str, tokens: &Tokens) -> Option<u16> {
+        let (scheme, _) = self.scheme_from_tokens(input, tokens)?;
+        self.default_port(scheme)
+    }
+
+    /// The port of a scheme according to the schemes known to this parser.
+    pub(crate) fn default_port(&self, scheme: &str) -> Option<u16> {
+        self.schemes.default_port(scheme)
     }
 }
 
@@ -103,6 +112,51 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_port_works_when_ipv6() {
+        let input = "http://[2001:db8::1]:8080/x";
+        let port = Parser::new(None).port(input);
+        assert_eq!(port.unwrap(), 8080);
+    }
+
+    #[test]
+    fn test_port_default_works_when_ipv6() {
+        let input = "https://[::1]/x";
+        let port = Parser::new(None).port(input);
+        assert_eq!(port.unwrap(), 443);
+    }
+
+    #[test]
+    fn test_port_is_none_when_out_of_range() {
+        let input = "https://www.example.co.uk:65536/";
+        let port = Parser::new(None).port(input);
+        assert!(port.is_none());
+    }
+
+    #[test]
+    fn test_explicit_port_from_tokens_fails_when_out_of_range() {
+        let parser = Parser::new(None);
+        let input = "https://www.example.co.uk:4294967296/";
+        let tokens = parser.tokenize(input);
+        assert_eq!(
+            parser.explicit_port_from_tokens(input, &tokens),
+            Err(ParseError::new(
+                ParseErrorKind::InvalidPort,
+                26,
+                "4294967296"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_explicit_port_from_tokens_is_none_when_default() {
+        let parser = Parser::new(None);
+        let input = "https://www.example.co.uk/";
+        let tokens = parser.tokenize(input);
+        assert_eq!(parser.explicit_port_from_tokens(input, &tokens), Ok(None));
+        assert_eq!(parser.default_port_from_tokens(input, &tokens), Some(443));
+    }
+
     #[test]
     fn test_port_works_when_colon_in_url() {
         let input = "http://en.wikipedia.org/wiki/Template:Welcome";
@@ -0,0 +1,207 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Snapshot of <https://publicsuffix.org/list/public_suffix_list.dat>, used unless the parser is given another list.
+const EMBEDDED_LIST: &str = include_str!("../../data/public_suffix_list.dat");
+
+/// The rules of a [Public Suffix List](https://publicsuffix.org/), deciding under which suffixes names can be registered.
+/// Both the ICANN and the private section of the list are taken into account.
+///
+/// # Example
+/// ```rust
+/// use url_parse::core::public_suffix::PublicSuffixList;
+/// let list = PublicSuffixList::embedded();
+/// assert_eq!(list.public_suffix("www.example.co.uk"), Some("co.uk"));
+/// assert_eq!(list.registrable_domain("www.example.co.uk"), Some("example.co.uk"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PublicSuffixList {
+    rules: HashSet<String>,
+    /// Rules of the form `*.suffix`, stored without the wildcard label.
+    wildcards: HashSet<String>,
+    /// Rules of the form `!name`, stored without the exclamation mark.
+    exceptions: HashSet<String>,
+}
+
+impl PublicSuffixList {
+    /// Parse a list in the format of `public_suffix_list.dat`: one rule per line, `//` starting a comment.
+    /// Internationalized rules also match their Punycode encoding.
+    ///
+    /// # Example
+    /// ```rust
+    /// use url_parse::core::public_suffix::PublicSuffixList;
+    /// let list = PublicSuffixList::parse("// my suffixes\ncom\n*.example.com\n!www.example.com");
+    /// assert_eq!(list.public_suffix("a.b.example.com"), Some("b.example.com"));
+    /// assert_eq!(list.public_suffix("www.example.com"), Some("example.com"));
+    /// ```
+    pub fn parse(list: &str) -> Self {
+        let mut result = PublicSuffixList::default();
+        for line in list.lines() {
+            let rule = match line.split_whitespace().next() {
+                Some(rule) if !rule.starts_with("//") => rule.to_lowercase(),
+                _ => continue,
+            };
+            if !rule.is_ascii() {
+                if let Ok(ascii_rule) = idna::domain_to_ascii(&rule) {
+                    result.insert(ascii_rule);
+                }
+            }
+            result.insert(rule);
+        }
+        result
+    }
+
+    fn insert(&mut self, rule: String) {
+        if let Some(exception) = rule.strip_prefix('!') {
+            self.exceptions.insert(exception.to_string());
+        } else if let Some(wildcard) = rule.strip_prefix("*.") {
+            self.wildcards.insert(wildcard.to_string());
+        } else {
+            self.rules.insert(rule);
+        }
+    }
+
+    /// Load an updated list from a local file, i.e. a fresh download of `public_suffix_list.dat`.
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        std::fs::read_to_string(path).map(|list| Self::parse(&list))
+    }
+
+    /// The snapshot of the list shipped with this crate. It is parsed once, on first use.
+    pub fn embedded() -> &'static Self {
+        static LIST: OnceLock<PublicSuffixList> = OnceLock::new();
+        LIST.get_or_init(|| Self::parse(EMBEDDED_LIST))
+    }
+
+    /// Find the public suffix of a host, i.e. the part under which names can be registered. If no rule matches,
+    /// the last label is the public suffix.
+    pub fn public_suffix<'a>(&self, host: &'a str) -> Option<&'a str> {
+        let start = self.public_suffix_start(host)?;
+        Some(&host[start..])
+    }
+
+    /// Find the registrable domain of a host (eTLD+1), which is the public suffix and the label in front of it.
+    /// A host which is a public suffix itself has no registrable domain.
+    pub fn registrable_domain<'a>(&self, host: &'a str) -> Option<&'a str> {
+        let start = self.public_suffix_start(host)?;
+        let dot = host[..start].strip_suffix('.')?;
+        Some(&host[dot.rfind('.').map_or(0, |pos| pos + 1)..])
+    }
+
+    /// Byte offset at which the public suffix of `host` starts, following the algorithm of publicsuffix.org:
+    /// exception rules win, otherwise the rule matching the most labels is used.
+    pub(crate) fn public_suffix_start(&self, host: &str) -> Option<usize> {
+        if host.is_empty() || host.starts_with('.') || host.ends_with('.') {
+            return None;
+        }
+        let lowercase;
+        let host = if host.chars().any(char::is_uppercase) {
+            lowercase = host.to_lowercase();
+            if lowercase.len() != host.len() {
+                return None;
+            }
+            lowercase.as_str()
+        } else {
+            host
+        };
+
+        let label_starts =
+            std::iter::once(0).chain(host.match_indices('.').map(|(pos, _)| pos + 1));
+        let mut result = None;
+        for start in label_starts {
+            let suffix = &host[start..];
+            if self.exceptions.contains(suffix) {
+                return suffix.find('.').map(|pos| start + pos + 1);
+            }
+            if result.is_none() && self.rules.contains(suffix) {
+                result = Some(start);
+            }
+            let is_wildcard = match suffix.split_once('.') {
+                Some((_, parent)) => self.wildcards.contains(parent),
+                None => false,
+            };
+            if result.is_none() && is_wildcard {
+                result = Some(start);
+            }
+        }
+        result.or_else(|| Some(host.rfind('.').map_or(0, |pos| pos + 1)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_public_suffix_works_when_typical() {
+        let list = PublicSuffixList::embedded();
+        assert_eq!(list.public_suffix("www.example.com"), Some("com"));
+        assert_eq!(list.public_suffix("a.b.example.co.uk"), Some("co.uk"));
+        assert_eq!(list.public_suffix("example.github.io"), Some("github.io"));
+    }
+
+    #[test]
+    fn test_public_suffix_works_when_unlisted() {
+        let list = PublicSuffixList::embedded();
+        assert_eq!(list.public_suffix("example.unlisted"), Some("unlisted"));
+        assert_eq!(list.public_suffix("localhost"), Some("localhost"));
+    }
+
+    #[test]
+    fn test_public_suffix_works_when_wildcard_and_exception() {
+        let list = PublicSuffixList::embedded();
+        assert_eq!(list.public_suffix("a.b.ck"), Some("b.ck"));
+        assert_eq!(list.public_suffix("www.ck"), Some("ck"));
+    }
+
+    #[test]
+    fn test_public_suffix_works_when_internationalized() {
+        let list = PublicSuffixList::embedded();
+        assert_eq!(list.public_suffix("example.公司.cn"), Some("公司.cn"));
+        assert_eq!(
+            list.public_suffix("example.xn--55qx5d.cn"),
+            Some("xn--55qx5d.cn")
+        );
+    }
+
+    #[test]
+    fn test_public_suffix_works_when_uppercase() {
+        let list = PublicSuffixList::embedded();
+        assert_eq!(list.public_suffix("WWW.Example.CO.UK"), Some("CO.UK"));
+    }
+
+    #[test]
+    fn test_registrable_domain_works_when_typical() {
+        let list = PublicSuffixList::embedded();
+        assert_eq!(
+            list.registrable_domain("a.b.example.co.uk"),
+            Some("example.co.uk")
+        );
+        assert_eq!(list.registrable_domain("example.com"), Some("example.com"));
+        assert_eq!(list.registrable_domain("www.ck"), Some("www.ck"));
+    }
+
+    #[test]
+    fn test_registrable_domain_is_none_when_public_suffix() {
+        let list = PublicSuffixList::embedded();
+        assert_eq!(list.registrable_domain("co.uk"), None);
+        assert_eq!(list.registrable_domain("com"), None);
+        assert_eq!(list.registrable_domain(""), None);
+    }
+
+    #[test]
+    fn test_from_file_works_when_typical() {
+        let path = std::env::temp_dir().join("url_parse_test_public_suffix_list.dat");
+        std::fs::write(&path, "// test\nexample\n").unwrap();
+        let list = PublicSuffixList::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(list.registrable_domain("a.b.example"), Some("b.example"));
+    }
+
+    #[test]
+    fn test_from_file_fails_when_missing() {
+        let result = PublicSuffixList::from_file("/nonexistent/public_suffix_list.dat");
+        assert!(result.is_err());
+    }
+}
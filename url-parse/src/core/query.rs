@@ -1,7 +1,8 @@
+use crate::core::tokenizer::Tokens;
 use crate::core::Parser;
 
 impl Parser {
-    /// Extract the query from the url.
+    /// Extract the query from the url. A `?` which is followed by nothing yields an empty query, unlike no `?` at all.
     ///
     /// # Example
     /// ```rust
@@ -11,27 +12,12 @@ impl Parser {
     /// assert_eq!(result, "docid=720&hl=en");
     /// ```
     pub fn query<'a>(&self, input: &'a str) -> Option<&'a str> {
-        let position_questionmark = input.find('?');
-        let position_pound = input.find('#');
-    
-        // Verifica se existe um '?' na string
-        if let Some(v) = position_questionmark {
-            // Define o índice final como o menor entre a posição do '#' e o comprimento da string
-            let end = match position_pound {
-                Some(p) if p > v => p, // Se '#' aparece depois de '?', usa a posição do '#'
-                _ => input.len(), // Caso contrário, usa o comprimento total da string
-            };
-    
-            // Verifica se end é maior que v + 1 para evitar slice inválido
-            if end > v + 1 {
-                let after = &input[v + 1..end];
-                return Some(after);
-            }
-        }
-        
-        None // Retorna None se não houver query string válida
+        self.query_from_tokens(input, &self.tokenize(input))
+    }
+
+    pub(crate) fn query_from_tokens<'a>(&self, input: &'a str, tokens: &Tokens) -> Option<&'a str> {
+        Some(&input[tokens.query.clone()?])
     }
-    
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
 use crate::core::scheme_separator::SchemeSeparator;
+use crate::core::tokenizer::Tokens;
 use crate::core::Parser;
 impl Parser {
     /// Extract the query from the url.
@@ -22,18 +23,16 @@ impl Parser {
     /// assert_eq!(scheme.unwrap(), ("https",  SchemeSeparator::Colon));
     /// ```
     pub fn scheme<'a>(&self, input: &'a str) -> Option<(&'a str, SchemeSeparator)> {
-        let split: Vec<&str> = input.split("://").collect();
-
-        if split.len() == 2 {
-            return Some((split[0], SchemeSeparator::ColonSlashSlash));
-        };
+        self.scheme_from_tokens(input, &self.tokenize(input))
+    }
 
-        let split: Vec<&str> = input.split(':').collect();
-        let scheme = self
-            .port_mappings
-            .keys()
-            .find(|&protocol| &split[0] == protocol)?;
-        Some((scheme, SchemeSeparator::Colon))
+    pub(crate) fn scheme_from_tokens<'a>(
+        &self,
+        input: &'a str,
+        tokens: &Tokens,
+    ) -> Option<(&'a str, SchemeSeparator)> {
+        let (range, separator) = tokens.scheme.clone()?;
+        Some((&input[range], separator))
     }
 }
 
@@ -0,0 +1,380 @@
+use crate::core::defaults::{default_port_mappings, OPAQUE_SCHEMES};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// What a parser knows about a scheme.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(any(feature = "json", feature = "toml"), derive(serde::Deserialize))]
+#[cfg_attr(any(feature = "json", feature = "toml"), serde(default))]
+pub struct SchemeInfo {
+    /// The port used when the url does not specify one.
+    pub default_port: Option<u16>,
+    pub description: String,
+    /// The WHATWG URL Standard treats the scheme as special, i.e. its urls always have an authority.
+    pub special: bool,
+    /// Its urls have a `/`-separated path, as opposed to an opaque one like `mailto:` urls.
+    pub hierarchical: bool,
+}
+
+impl Default for SchemeInfo {
+    fn default() -> Self {
+        SchemeInfo {
+            default_port: None,
+            description: String::new(),
+            special: false,
+            hierarchical: true,
+        }
+    }
+}
+
+impl SchemeInfo {
+    pub fn with_default_port(mut self, default_port: u16) -> Self {
+        self.default_port = Some(default_port);
+        self
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    pub fn with_special(mut self, special: bool) -> Self {
+        self.special = special;
+        self
+    }
+
+    pub fn with_hierarchical(mut self, hierarchical: bool) -> Self {
+        self.hierarchical = hierarchical;
+        self
+    }
+}
+
+/// The schemes a parser knows, keyed by their name. The default registry holds the well-known schemes of
+/// `default_port_mappings()`, `file` and well-known schemes with an opaque path such as `mailto`. Further schemes can
+/// be registered at runtime or loaded from a file.
+///
+/// # Example
+/// ```rust
+/// use url_parse::core::Parser;
+/// use url_parse::core::scheme_registry::{SchemeInfo, SchemeRegistry};
+/// let mut schemes = SchemeRegistry::default();
+/// schemes.register("myschema", SchemeInfo::default().with_default_port(8888));
+/// let parser = Parser::new(None).with_schemes(schemes);
+/// let url = parser.parse("myschema://example.com/").unwrap();
+/// assert_eq!(url.port_or_known_default(), Some(8888));
+/// assert_eq!(parser.parse("https://example.com/").unwrap().port_or_known_default(), Some(443));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(any(feature = "json", feature = "toml"), derive(serde::Deserialize))]
+#[cfg_attr(
+    any(feature = "json", feature = "toml"),
+    serde(from = "HashMap<String, SchemeInfo>")
+)]
+pub struct SchemeRegistry {
+    schemes: HashMap<String, SchemeInfo>,
+}
+
+impl Default for SchemeRegistry {
+    fn default() -> Self {
+        let mut registry = SchemeRegistry::empty();
+        registry.extend(port_mapping_schemes(default_port_mappings()));
+        let mut registry = registry.with_scheme(
+            "file",
+            SchemeInfo::default()
+                .with_description("Host-specific File Names")
+                .with_special(true),
+        );
+        for (scheme, description) in OPAQUE_SCHEMES {
+            let info = SchemeInfo::default()
+                .with_description(description)
+                .with_hierarchical(false);
+            registry.register(scheme, info);
+        }
+        registry
+    }
+}
+
+impl SchemeRegistry {
+    /// A registry without any scheme.
+    pub fn empty() -> Self {
+        SchemeRegistry {
+            schemes: HashMap::new(),
+        }
+    }
+
+    /// Add a scheme, returning the previous definition if the scheme was already known. Schemes are
+    /// case-insensitive, so `HTTP` replaces `http`.
+    pub fn register(&mut self, scheme: &str, info: SchemeInfo) -> Option<SchemeInfo> {
+        self.schemes.insert(key(scheme).into_owned(), info)
+    }
+
+    pub fn unregister(&mut self, scheme: &str) -> Option<SchemeInfo> {
+        self.schemes.remove(key(scheme).as_ref())
+    }
+
+    pub fn with_scheme(mut self, scheme: &str, info: SchemeInfo) -> Self {
+        self.register(scheme, info);
+        self
+    }
+
+    /// Look up a scheme, ignoring ASCII case.
+    ///
+    /// # Example
+    /// ```rust
+    /// use url_parse::core::scheme_registry::SchemeRegistry;
+    /// let schemes = SchemeRegistry::default();
+    /// assert_eq!(schemes.default_port("HTTPS"), Some(443));
+    /// assert!(!schemes.get("MailTo").unwrap().hierarchical);
+    /// ```
+    pub fn get(&self, scheme: &str) -> Option<&SchemeInfo> {
+        self.schemes.get(key(scheme).as_ref())
+    }
+
+    pub fn contains(&self, scheme: &str) -> bool {
+        self.get(scheme).is_some()
+    }
+
+    pub fn default_port(&self, scheme: &str) -> Option<u16> {
+        self.get(scheme)?.default_port
+    }
+
+    /// The schemes in lowercase with their definitions.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &SchemeInfo)> {
+        self.schemes
+            .iter()
+            .map(|(scheme, info)| (scheme.as_str(), info))
+    }
+
+    pub fn len(&self) -> usize {
+        self.schemes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.schemes.is_empty()
+    }
+
+    /// Parse scheme definitions from JSON, an object mapping each scheme to its fields. Missing fields take the
+    /// values of `SchemeInfo::default()`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use url_parse::core::scheme_registry::SchemeRegistry;
+    /// let json = r#"{"myschema": {"default_port": 8888, "description": "My custom schema"}}"#;
+    /// let schemes = SchemeRegistry::from_json(json).unwrap();
+    /// assert_eq!(schemes.default_port("myschema"), Some(8888));
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn from_json(definitions: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(definitions)
+    }
+
+    /// Parse scheme definitions from TOML, a table per scheme. Missing fields take the values of
+    /// `SchemeInfo::default()`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use url_parse::core::scheme_registry::SchemeRegistry;
+    /// let toml = "[myschema]\ndefault_port = 8888\nhierarchical = false\n";
+    /// let schemes = SchemeRegistry::from_toml(toml).unwrap();
+    /// assert!(!schemes.get("myschema").unwrap().hierarchical);
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn from_toml(definitions: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(definitions)
+    }
+
+    /// Load scheme definitions from a `.json` or `.toml` file, depending on its extension. The formats are enabled
+    /// by the `json` and `toml` features.
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let invalid = |error: String| std::io::Error::new(std::io::ErrorKind::InvalidData, error);
+        match path.extension().and_then(|extension| extension.to_str()) {
+            #[cfg(feature = "json")]
+            Some("json") => {
+                Self::from_json(&std::fs::read_to_string(path)?).map_err(|e| invalid(e.to_string()))
+            }
+            #[cfg(feature = "toml")]
+            Some("toml") => {
+                Self::from_toml(&std::fs::read_to_string(path)?).map_err(|e| invalid(e.to_string()))
+            }
+            _ => Err(invalid(format!(
+                "unsupported scheme definitions file: {}",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// Extend the registry, replacing the definitions of schemes which are already known.
+impl Extend<(String, SchemeInfo)> for SchemeRegistry {
+    fn extend<I: IntoIterator<Item = (String, SchemeInfo)>>(&mut self, iter: I) {
+        for (scheme, info) in iter {
+            self.register(&scheme, info);
+        }
+    }
+}
+
+/// Collect scheme definitions, such as those deserialized from a file.
+impl From<HashMap<String, SchemeInfo>> for SchemeRegistry {
+    fn from(schemes: HashMap<String, SchemeInfo>) -> Self {
+        let mut registry = SchemeRegistry::empty();
+        registry.extend(schemes);
+        registry
+    }
+}
+
+impl IntoIterator for SchemeRegistry {
+    type Item = (String, SchemeInfo);
+    type IntoIter = std::collections::hash_map::IntoIter<String, SchemeInfo>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.schemes.into_iter()
+    }
+}
+
+/// Extend the default registry with port mappings as taken by `Parser::new()`, replacing the definitions of schemes
+/// which are already known.
+impl From<HashMap<&'static str, (u16, &'static str)>> for SchemeRegistry {
+    fn from(port_mappings: HashMap<&'static str, (u16, &'static str)>) -> Self {
+        let mut registry = SchemeRegistry::default();
+        registry.extend(port_mapping_schemes(port_mappings));
+        registry
+    }
+}
+
+/// The definitions of port mappings. The schemes of the WHATWG URL Standard are marked as special.
+fn port_mapping_schemes(
+    port_mappings: HashMap<&'static str, (u16, &'static str)>,
+) -> impl Iterator<Item = (String, SchemeInfo)> {
+    port_mappings
+        .into_iter()
+        .map(|(scheme, (port, description))| {
+            let scheme = key(scheme).into_owned();
+            let info = SchemeInfo::default()
+                .with_default_port(port)
+                .with_description(description)
+                .with_special(matches!(
+                    scheme.as_str(),
+                    "ftp" | "http" | "https" | "ws" | "wss"
+                ));
+            (scheme, info)
+        })
+}
+
+/// Schemes are case-insensitive, so they are stored and looked up in lowercase.
+fn key(scheme: &str) -> Cow<'_, str> {
+    match scheme.bytes().any(|b| b.is_ascii_uppercase()) {
+        true => Cow::Owned(scheme.to_ascii_lowercase()),
+        false => Cow::Borrowed(scheme),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_works_when_well_known_scheme() {
+        let schemes = SchemeRegistry::default();
+        let https = schemes.get("https").unwrap();
+        assert_eq!(https.default_port, Some(443));
+        assert!(https.special);
+        assert!(https.hierarchical);
+        assert!(!schemes.get("ssh").unwrap().special);
+        assert!(!schemes.get("mailto").unwrap().hierarchical);
+        assert_eq!(schemes.default_port("file"), None);
+        assert_eq!(
+            schemes.len(),
+            default_port_mappings().len() + OPAQUE_SCHEMES.len() + 1
+        );
+    }
+
+    #[test]
+    fn test_register_works_when_extending_defaults() {
+        let mut schemes = SchemeRegistry::default();
+        let previous = schemes.register("http", SchemeInfo::default().with_default_port(8080));
+        schemes.register("gopher", SchemeInfo::default().with_default_port(70));
+        assert_eq!(previous.unwrap().default_port, Some(80));
+        assert_eq!(schemes.default_port("http"), Some(8080));
+        assert_eq!(schemes.default_port("gopher"), Some(70));
+        assert_eq!(schemes.default_port("https"), Some(443));
+    }
+
+    #[test]
+    fn test_from_works_when_port_mappings() {
+        let mappings = HashMap::from([
+            ("myschema", (8888, "My custom schema")),
+            ("http", (8080, "Proxy")),
+        ]);
+        let schemes = SchemeRegistry::from(mappings);
+        assert_eq!(schemes.default_port("myschema"), Some(8888));
+        assert_eq!(schemes.default_port("http"), Some(8080));
+        assert!(schemes.get("http").unwrap().special);
+        assert_eq!(schemes.default_port("https"), Some(443));
+        assert!(schemes.get("file").unwrap().special);
+        assert!(!schemes.get("mailto").unwrap().hierarchical);
+    }
+
+    #[test]
+    fn test_get_works_when_case_differs() {
+        let mut schemes = SchemeRegistry::default();
+        assert_eq!(schemes.default_port("HTTPS"), Some(443));
+        assert!(!schemes.get("MAILTO").unwrap().hierarchical);
+        schemes.register("MySchema", SchemeInfo::default().with_default_port(8888));
+        assert_eq!(schemes.default_port("myschema"), Some(8888));
+        assert!(schemes.iter().any(|(scheme, _)| scheme == "myschema"));
+        assert!(schemes.unregister("MYSCHEMA").is_some());
+        assert!(!schemes.contains("MySchema"));
+    }
+
+    #[test]
+    fn test_unregister_works_when_typical() {
+        let mut schemes = SchemeRegistry::default();
+        assert!(schemes.unregister("ftp").is_some());
+        assert!(!schemes.contains("ftp"));
+        assert!(SchemeRegistry::empty().is_empty());
+    }
+
+    #[test]
+    fn test_extend_works_when_loaded_definitions() {
+        let loaded = SchemeRegistry::empty()
+            .with_scheme("mailto", SchemeInfo::default().with_hierarchical(false));
+        let mut schemes = SchemeRegistry::default();
+        schemes.extend(loaded);
+        assert!(!schemes.get("mailto").unwrap().hierarchical);
+        assert!(schemes.contains("https"));
+    }
+
+    #[test]
+    fn test_from_file_fails_when_unsupported_extension() {
+        let result = SchemeRegistry::from_file("schemes.txt");
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_from_json_works_when_partial_fields() {
+        let schemes = SchemeRegistry::from_json(r#"{"myschema": {"special": true}}"#).unwrap();
+        let expected = SchemeInfo::default().with_special(true);
+        assert_eq!(schemes.get("myschema"), Some(&expected));
+        assert!(SchemeRegistry::from_json(r#"{"myschema": {"default_port": 70000}}"#).is_err());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_from_file_works_when_toml() {
+        let path = std::env::temp_dir().join("url_parse_schemes.toml");
+        std::fs::write(
+            &path,
+            "[myschema]\ndefault_port = 8888\ndescription = \"My custom schema\"\n",
+        )
+        .unwrap();
+        let result = SchemeRegistry::from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        let expected = SchemeInfo::default()
+            .with_default_port(8888)
+            .with_description("My custom schema");
+        assert_eq!(result.unwrap().get("myschema"), Some(&expected));
+    }
+}
@@ -0,0 +1,228 @@
+use crate::core::host::Host;
+use crate::core::scheme_separator::SchemeSeparator;
+use crate::core::validation::{validate_host_value, validate_scheme};
+use crate::core::Parser;
+use crate::error::{ParseError, ParseErrorKind};
+use crate::url::Url;
+
+impl Parser {
+    /// Replace the scheme of `url`, together with its default port according to the schemes known to this parser.
+    /// A scheme which is registered as not hierarchical, like `mailto`, cannot replace the scheme of an url with a
+    /// hierarchical path, and the other way around.
+    ///
+    /// # Example
+    /// ```rust
+    /// use url_parse::core::Parser;
+    /// use url_parse::core::scheme_registry::SchemeInfo;
+    /// use url_parse::error::ParseErrorKind;
+    /// let mut parser = Parser::new(None);
+    /// parser.register_scheme("myschema", SchemeInfo::default().with_default_port(8888));
+    /// let mut url = parser.parse("https://example.com/x").unwrap();
+    /// parser.set_scheme(&mut url, "myschema").unwrap();
+    /// assert_eq!(url.port_or_known_default(), Some(8888));
+    /// let error = parser.set_scheme(&mut url, "mailto").unwrap_err();
+    /// assert_eq!(error.kind, ParseErrorKind::OpaquePath);
+    /// ```
+    pub fn set_scheme(&self, url: &mut Url, scheme: &str) -> Result<(), ParseError> {
+        validate_scheme(scheme, 0..scheme.len())?;
+        let info = self.schemes.get(scheme);
+        let hierarchical = info.is_none_or(|info| info.hierarchical);
+        if hierarchical == url.opaque_path.is_some() {
+            return Err(ParseError::new(ParseErrorKind::OpaquePath, 0, scheme));
+        }
+        url.default_port = self.default_port(scheme);
+        url.scheme = Some(scheme.to_string());
+        Ok(())
+    }
+
+    /// Replace the host of `url`, splitting it into subdomain, domain and top level domain like `parse()` does. Urls
+    /// with an opaque path, like `mailto:` urls, cannot have a host.
+    ///
+    /// # Example
+    /// ```rust
+    /// use url_parse::core::Parser;
+    /// use url_parse::error::ParseErrorKind;
+    /// let parser = Parser::new(None);
+    /// let mut url = parser.parse("https://example.com/").unwrap();
+    /// parser.set_host(&mut url, "www.example.co.uk").unwrap();
+    /// assert_eq!(url.subdomain.as_deref(), Some("www"));
+    /// let mut mail = parser.parse("mailto:a@b.com").unwrap();
+    /// let error = parser.set_host(&mut mail, "x.com").unwrap_err();
+    /// assert_eq!(error.kind, ParseErrorKind::OpaquePath);
+    /// ```
+    pub fn set_host(&self, url: &mut Url, host: &str) -> Result<(), ParseError> {
+        if url.opaque_path.is_some() {
+            return Err(ParseError::new(ParseErrorKind::OpaquePath, 0, host));
+        }
+        if host.is_empty() {
+            return Err(ParseError::new(ParseErrorKind::EmptyHost, 0, ""));
+        }
+        validate_host_value(host)?;
+        let classified = Host::classify(host);
+        let split_host = match &classified {
+            Some(Host::Domain(ascii)) => ascii.as_str(),
+            _ => host,
+        };
+        let domain = self.domain_of_host(split_host);
+        url.subdomain = domain.subdomain.map(str::to_string);
+        url.domain = domain.domain.map(str::to_string);
+        url.top_level_domain = domain.top_level_domain.map(str::to_string);
+        url.host = classified;
+        if url.scheme.is_some() {
+            url.scheme_separator = Some(SchemeSeparator::ColonSlashSlash);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_set_scheme_works_when_default_port() {
+        let parser = Parser::new(None);
+        let mut url = parser.parse("http://example.com/").unwrap();
+        parser.set_scheme(&mut url, "https").unwrap();
+        assert_eq!(url.port_or_known_default(), Some(443));
+        assert_eq!(url, parser.parse("https://example.com/").unwrap());
+    }
+
+    #[test]
+    fn test_set_scheme_keeps_explicit_port() {
+        let parser = Parser::new(None);
+        let mut url = parser.parse("http://example.com:8080/").unwrap();
+        parser.set_scheme(&mut url, "https").unwrap();
+        assert_eq!(url.port, Some(8080));
+    }
+
+    #[test]
+    fn test_set_scheme_fails_when_invalid() {
+        let parser = Parser::new(None);
+        let mut url = Url::empty();
+        let result = parser.set_scheme(&mut url, "1http");
+        assert_eq!(
+            result,
+            Err(ParseError::new(ParseErrorKind::InvalidScheme, 0, "1http"))
+        );
+        assert_eq!(url.scheme, None);
+    }
+
+    #[test]
+    fn test_set_host_works_when_internationalized() {
+        let parser = Parser::new(None);
+        let mut url = parser.parse("https://example.com/").unwrap();
+        parser.set_host(&mut url, "www.münchen.de").unwrap();
+        assert_eq!(url, parser.parse("https://www.münchen.de/").unwrap());
+    }
+
+    #[test]
+    fn test_set_host_works_when_ip() {
+        let parser = Parser::new(None);
+        let mut url = parser.parse("https://example.com/").unwrap();
+        parser.set_host(&mut url, "[::1]").unwrap();
+        assert_eq!(url, parser.parse("https://[::1]/").unwrap());
+        parser.set_host(&mut url, "127.0.0.1").unwrap();
+        assert_eq!(url, parser.parse("https://127.0.0.1/").unwrap());
+    }
+
+    #[test]
+    fn test_set_host_fails_when_invalid() {
+        let parser = Parser::new(None);
+        let mut url = parser.parse("https://example.com/").unwrap();
+        let result = parser.set_host(&mut url, "exa mple.com");
+        assert_eq!(
+            result,
+            Err(ParseError::new(
+                ParseErrorKind::InvalidHostCharacter,
+                3,
+                " "
+            ))
+        );
+        assert_eq!(
+            parser.set_host(&mut url, "").unwrap_err().kind,
+            ParseErrorKind::EmptyHost
+        );
+        assert_eq!(url.host_str().unwrap(), "example.com");
+    }
+
+    #[test]
+    fn test_set_host_fails_when_delimiter_would_inject_host() {
+        let parser = Parser::new(None);
+        let mut url = parser.parse("https://example.com/x").unwrap();
+        for (host, position, snippet) in [
+            ("evil.com@good.com", 8, "@"),
+            ("evil.com/good.com", 8, "/"),
+            ("evil.com:8080", 8, ":"),
+            ("evil.com?good.com", 8, "?"),
+            ("evil.com#good.com", 8, "#"),
+            ("evil%2Ecom", 4, "%"),
+        ] {
+            let result = parser.set_host(&mut url, host);
+            assert_eq!(
+                result,
+                Err(ParseError::new(
+                    ParseErrorKind::InvalidHostCharacter,
+                    position,
+                    snippet
+                )),
+                "{}",
+                host
+            );
+        }
+        assert_eq!(url.serialize(), "https://example.com/x");
+    }
+
+    #[test]
+    fn test_set_scheme_works_when_registered_on_parser() {
+        let mut mappings = HashMap::new();
+        mappings.insert("myschema", (8888, "My custom schema"));
+        let parser = Parser::new(Some(mappings));
+        let mut url = parser.parse("http://example.com/").unwrap();
+        parser.set_scheme(&mut url, "myschema").unwrap();
+        assert_eq!(url.default_port, Some(8888));
+    }
+
+    #[test]
+    fn test_set_scheme_fails_when_switching_between_opaque_and_hierarchical() {
+        let parser = Parser::new(None);
+        let mut url = parser.parse("https://example.com/x").unwrap();
+        let result = parser.set_scheme(&mut url, "mailto");
+        assert_eq!(
+            result,
+            Err(ParseError::new(ParseErrorKind::OpaquePath, 0, "mailto"))
+        );
+        assert_eq!(url.serialize(), "https://example.com/x");
+
+        let mut mail = parser.parse("mailto:a@b.com").unwrap();
+        let result = parser.set_scheme(&mut mail, "https");
+        assert_eq!(
+            result,
+            Err(ParseError::new(ParseErrorKind::OpaquePath, 0, "https"))
+        );
+        parser.set_scheme(&mut mail, "sms").unwrap();
+        assert_eq!(mail.serialize(), "sms:a@b.com");
+    }
+
+    #[test]
+    fn test_set_host_fails_when_opaque() {
+        let parser = Parser::new(None);
+        let mut url = parser.parse("mailto:a@b.com").unwrap();
+        let result = parser.set_host(&mut url, "x.com");
+        assert_eq!(
+            result,
+            Err(ParseError::new(ParseErrorKind::OpaquePath, 0, "x.com"))
+        );
+        assert_eq!(url.serialize(), "mailto:a@b.com");
+    }
+
+    #[test]
+    fn test_set_host_works_when_no_host_before() {
+        let parser = Parser::new(None);
+        let mut url = parser.parse("ssh:/x").unwrap();
+        parser.set_host(&mut url, "example.com").unwrap();
+        assert_eq!(url.serialize(), "ssh://example.com/x");
+        assert_eq!(url, parser.parse("ssh://example.com/x").unwrap());
+    }
+}
@@ -0,0 +1,149 @@
+use crate::core::percent_encoding::percent_decode;
+use std::borrow::Cow;
+
+/// The decoded payload of a `data:` url (RFC 2397), following the processing of the WHATWG Fetch Standard.
+///
+/// # Example
+/// ```rust
+/// use url_parse::core::Parser;
+/// let url = Parser::new(None).parse("data:text/plain;base64,SGVsbG8sIFdvcmxkIQ==").unwrap();
+/// let data = url.data_url().unwrap();
+/// assert_eq!(data.mime_type, "text/plain");
+/// assert!(data.base64);
+/// assert_eq!(data.text(), "Hello, World!");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataUrl {
+    /// The media type with its parameters, `text/plain;charset=US-ASCII` if none is given.
+    pub mime_type: String,
+    /// The body was base64-encoded.
+    pub base64: bool,
+    pub body: Vec<u8>,
+}
+
+impl DataUrl {
+    /// Decode everything following `data:`, i.e. the optional media type, the optional `;base64` marker, a comma and
+    /// the percent-encoded body. Fails if the comma is missing or the body is not valid base64.
+    ///
+    /// # Example
+    /// ```rust
+    /// use url_parse::data_url::DataUrl;
+    /// let data = DataUrl::parse(",A%20brief%20note").unwrap();
+    /// assert_eq!(data.mime_type, "text/plain;charset=US-ASCII");
+    /// assert_eq!(data.body, b"A brief note");
+    /// ```
+    pub fn parse(payload: &str) -> Option<Self> {
+        let (media_type, body) = payload.split_once(',')?;
+        let mut mime_type = media_type.trim_matches(|c: char| c.is_ascii_whitespace());
+        let base64 = match mime_type.rsplit_once(';') {
+            Some((rest, marker)) if marker.trim_start().eq_ignore_ascii_case("base64") => {
+                mime_type = rest.trim_end_matches(|c: char| c.is_ascii_whitespace());
+                true
+            }
+            _ => false,
+        };
+        let mime_type = match mime_type {
+            "" => "text/plain;charset=US-ASCII".to_string(),
+            parameters if parameters.starts_with(';') => format!("text/plain{}", parameters),
+            mime_type => mime_type.to_string(),
+        };
+        let mut body = percent_decode(body.as_bytes());
+        if base64 {
+            body = forgiving_base64_decode(&body)?;
+        }
+        Some(DataUrl {
+            mime_type,
+            base64,
+            body,
+        })
+    }
+
+    /// The body as text, replacing invalid UTF-8 with `U+FFFD`.
+    pub fn text(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.body)
+    }
+}
+
+/// Decode base64, ignoring ASCII whitespace and accepting missing padding.
+fn forgiving_base64_decode(input: &[u8]) -> Option<Vec<u8>> {
+    let mut data: Vec<u8> = input
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+    if data.len().is_multiple_of(4) {
+        for _ in 0..2 {
+            if data.last() == Some(&b'=') {
+                data.pop();
+            }
+        }
+    }
+    if data.len() % 4 == 1 {
+        return None;
+    }
+    let mut result = Vec::with_capacity(data.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in data {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        buffer = buffer << 6 | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            result.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forgiving_base64_decode_works_when_unpadded() {
+        assert_eq!(forgiving_base64_decode(b"SGk=").unwrap(), b"Hi");
+        assert_eq!(forgiving_base64_decode(b"SGk").unwrap(), b"Hi");
+        assert_eq!(
+            forgiving_base64_decode(b" S G V s\nb G 8 ").unwrap(),
+            b"Hello"
+        );
+        assert_eq!(forgiving_base64_decode(b"").unwrap(), b"");
+    }
+
+    #[test]
+    fn test_forgiving_base64_decode_is_none_when_invalid() {
+        assert!(forgiving_base64_decode(b"SGVsb").is_none());
+        assert!(forgiving_base64_decode(b"SG=k").is_none());
+        assert!(forgiving_base64_decode(b"S===").is_none());
+    }
+
+    #[test]
+    fn test_parse_works_when_parameters_only() {
+        let result = DataUrl::parse(";charset=utf-8,caf%C3%A9").unwrap();
+        assert_eq!(result.mime_type, "text/plain;charset=utf-8");
+        assert!(!result.base64);
+        assert_eq!(result.text(), "café");
+    }
+
+    #[test]
+    fn test_parse_works_when_base64_marker_case_insensitive() {
+        let result = DataUrl::parse("image/png ; BASE64,iVBORw0K").unwrap();
+        assert_eq!(result.mime_type, "image/png");
+        assert!(result.base64);
+        assert_eq!(result.body, b"\x89PNG\r\n");
+    }
+
+    #[test]
+    fn test_parse_is_none_when_no_comma() {
+        assert!(DataUrl::parse("text/plain;base64").is_none());
+    }
+}
@@ -1,11 +1,100 @@
 use core::fmt::Display;
 
-#[derive(Debug)]
-pub struct ParseError {}
+/// The reason an url was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The scheme is empty or contains characters other than ASCII alphanumerics, `+`, `-` and `.`.
+    InvalidScheme,
+    /// The url is relative, but no base url was given to resolve it against.
+    MissingBase,
+    /// The scheme requires a host, but none was given.
+    EmptyHost,
+    /// The host contains a character which may not appear in a host.
+    InvalidHostCharacter,
+    /// The host is not a valid domain, e.g. an internationalized domain which cannot be converted to ASCII.
+    InvalidDomain,
+    /// The host looks like an IPv4 address, but is not a valid one.
+    InvalidIpv4,
+    /// The host is an IPv6 literal in brackets, but not a valid one.
+    InvalidIpv6,
+    /// The port is not a number or exceeds 65535.
+    InvalidPort,
+    /// A `%` is not followed by two hexadecimal digits.
+    InvalidPercentEncoding,
+    /// A file path to be converted into a `file:` url is not absolute.
+    RelativeFilePath,
+    /// A host, credentials or a port were given for an url with an opaque path, like a `mailto:` url, or a scheme
+    /// switches between an opaque and a hierarchical path.
+    OpaquePath,
+    /// The input is empty.
+    EmptyInput,
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let description = match self {
+            ParseErrorKind::InvalidScheme => "invalid scheme",
+            ParseErrorKind::MissingBase => "relative url without a base",
+            ParseErrorKind::EmptyHost => "empty host",
+            ParseErrorKind::InvalidHostCharacter => "invalid host character",
+            ParseErrorKind::InvalidDomain => "invalid domain",
+            ParseErrorKind::InvalidIpv4 => "invalid IPv4 address",
+            ParseErrorKind::InvalidIpv6 => "invalid IPv6 address",
+            ParseErrorKind::InvalidPort => "invalid port",
+            ParseErrorKind::InvalidPercentEncoding => "invalid percent-encoding",
+            ParseErrorKind::RelativeFilePath => "relative file path",
+            ParseErrorKind::OpaquePath => "authority with an opaque path",
+            ParseErrorKind::EmptyInput => "empty input",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+/// An url which could not be parsed, with the byte offset into the input and the offending part of it.
+///
+/// # Example
+/// ```rust
+/// use url_parse::core::Parser;
+/// use url_parse::error::ParseErrorKind;
+/// let error = Parser::new(None).parse("https://example.com:99999/").unwrap_err();
+/// assert_eq!(error.kind, ParseErrorKind::InvalidPort);
+/// assert_eq!(error.position, 20);
+/// assert_eq!(error.snippet, "99999");
+/// assert_eq!(error.to_string(), "invalid port at byte 20: \"99999\"");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub position: usize,
+    pub snippet: String,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, position: usize, snippet: impl Into<String>) -> Self {
+        ParseError {
+            kind,
+            position,
+            snippet: snippet.into(),
+        }
+    }
+}
+
+/// Hand an error which is recovered from, instead of being returned, to the `log` crate when the `log` feature is
+/// enabled. The library never writes to stdout or stderr itself.
+pub(crate) fn report(error: &ParseError) {
+    #[cfg(feature = "log")]
+    log::warn!("{}", error);
+    #[cfg(not(feature = "log"))]
+    let _ = error;
+}
 
 impl Display for ParseError {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        write!(f, "ParseError")
+        write!(
+            f,
+            "{} at byte {}: {:?}",
+            self.kind, self.position, self.snippet
+        )
     }
 }
 
@@ -13,7 +102,7 @@ impl std::error::Error for ParseError {}
 
 #[test]
 fn test_err_display() {
-    let e = ParseError {};
+    let e = ParseError::new(ParseErrorKind::InvalidHostCharacter, 10, " ");
     let x = format!("{e}");
-    assert_eq!(x, "ParseError")
+    assert_eq!(x, "invalid host character at byte 10: \" \"")
 }
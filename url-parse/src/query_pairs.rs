@@ -0,0 +1,224 @@
+use crate::core::percent_encoding::percent_decode;
+
+/// The name-value pairs of an `application/x-www-form-urlencoded` query, in the order they appear. Duplicate names
+/// are kept, as in `tag=a&tag=b`.
+///
+/// # Example
+/// ```rust
+/// use url_parse::query_pairs::QueryPairs;
+/// let mut pairs = QueryPairs::parse("q=caf%C3%A9+au+lait&tag=a&tag=b");
+/// assert_eq!(pairs.get("q"), Some("café au lait"));
+/// assert_eq!(pairs.get_all("tag"), vec!["a", "b"]);
+///
+/// pairs.remove("tag");
+/// pairs.append("page", "2");
+/// assert_eq!(pairs.to_string(), "q=caf%C3%A9+au+lait&page=2");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryPairs {
+    pairs: Vec<(String, String)>,
+}
+
+impl QueryPairs {
+    /// Parse a query without its leading `?`. Empty sequences between `&` are skipped, a name without `=` gets an
+    /// empty value, `+` is decoded as a space and invalid UTF-8 is replaced by `U+FFFD`.
+    pub fn parse(query: &str) -> Self {
+        let pairs = query
+            .split('&')
+            .filter(|sequence| !sequence.is_empty())
+            .map(|sequence| {
+                let (name, value) = sequence.split_once('=').unwrap_or((sequence, ""));
+                (decode(name), decode(value))
+            })
+            .collect();
+        QueryPairs { pairs }
+    }
+
+    /// The value of the first pair with the given name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The values of all pairs with the given name, in order.
+    pub fn get_all(&self, name: &str) -> Vec<&str> {
+        self.pairs
+            .iter()
+            .filter(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    /// Check whether a pair with the given name exists.
+    pub fn contains(&self, name: &str) -> bool {
+        self.pairs.iter().any(|(n, _)| n == name)
+    }
+
+    /// Add a pair at the end, keeping existing pairs with the same name.
+    pub fn append(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.pairs.push((name.into(), value.into()));
+    }
+
+    /// Replace the value of the first pair with the given name and remove the others, or append the pair if the name
+    /// is new.
+    ///
+    /// # Example
+    /// ```rust
+    /// use url_parse::query_pairs::QueryPairs;
+    /// let mut pairs = QueryPairs::parse("a=1&b=2&a=3");
+    /// pairs.set("a", "4");
+    /// assert_eq!(pairs.to_string(), "a=4&b=2");
+    /// ```
+    pub fn set(&mut self, name: &str, value: impl Into<String>) {
+        match self.pairs.iter().position(|(n, _)| n == name) {
+            Some(first) => {
+                self.pairs[first].1 = value.into();
+                let mut index = 0;
+                self.pairs.retain(|(n, _)| {
+                    index += 1;
+                    index - 1 == first || n != name
+                });
+            }
+            None => self.append(name, value),
+        }
+    }
+
+    /// Remove all pairs with the given name.
+    pub fn remove(&mut self, name: &str) {
+        self.pairs.retain(|(n, _)| n != name);
+    }
+
+    /// Sort the pairs by name. The sort is stable, so pairs with the same name keep their relative order.
+    ///
+    /// # Example
+    /// ```rust
+    /// use url_parse::query_pairs::QueryPairs;
+    /// let mut pairs = QueryPairs::parse("b=1&a=2&b=0");
+    /// pairs.sort();
+    /// assert_eq!(pairs.to_string(), "a=2&b=1&b=0");
+    /// ```
+    pub fn sort(&mut self) {
+        self.pairs.sort_by(|(a, _), (b, _)| a.cmp(b));
+    }
+
+    /// Iterate over the decoded name-value pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+
+impl<N: Into<String>, V: Into<String>> FromIterator<(N, V)> for QueryPairs {
+    fn from_iter<I: IntoIterator<Item = (N, V)>>(iter: I) -> Self {
+        let pairs = iter
+            .into_iter()
+            .map(|(name, value)| (name.into(), value.into()))
+            .collect();
+        QueryPairs { pairs }
+    }
+}
+
+/// Serialize the pairs with the `application/x-www-form-urlencoded` serializer: a space becomes `+`, and every byte
+/// except ASCII alphanumerics and `*-._` is percent-encoded.
+impl std::fmt::Display for QueryPairs {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        for (i, (name, value)) in self.pairs.iter().enumerate() {
+            if i > 0 {
+                fmt.write_str("&")?;
+            }
+            write!(fmt, "{}={}", encode(name), encode(value))?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn decode(input: &str) -> String {
+    let bytes = input.replace('+', " ");
+    String::from_utf8_lossy(&percent_decode(bytes.as_bytes())).into_owned()
+}
+
+fn encode(input: &str) -> String {
+    let mut result = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b' ' => result.push('+'),
+            b'*' | b'-' | b'.' | b'_' => result.push(byte as char),
+            _ if byte.is_ascii_alphanumeric() => result.push(byte as char),
+            _ => result += &format!("%{:02X}", byte),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_works_when_typical() {
+        let result = QueryPairs::parse("docid=720&hl=en");
+        let expected: QueryPairs = [("docid", "720"), ("hl", "en")].into_iter().collect();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_parse_works_when_empty_sequences_and_missing_value() {
+        let result = QueryPairs::parse("&a&&b=&=c&");
+        let expected: QueryPairs = [("a", ""), ("b", ""), ("", "c")].into_iter().collect();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_parse_works_when_encoded() {
+        let result = QueryPairs::parse("a%2Bb=1+%2B+1%3D2&x=%FF");
+        assert_eq!(result.get("a+b"), Some("1 + 1=2"));
+        assert_eq!(result.get("x"), Some("\u{FFFD}"));
+    }
+
+    #[test]
+    fn test_get_works_when_duplicate_names() {
+        let result = QueryPairs::parse("tag=a&x=1&tag=b");
+        assert_eq!(result.get("tag"), Some("a"));
+        assert_eq!(result.get_all("tag"), vec!["a", "b"]);
+        assert_eq!(result.get("missing"), None);
+        assert!(result.get_all("missing").is_empty());
+    }
+
+    #[test]
+    fn test_set_works_when_new_name() {
+        let mut pairs = QueryPairs::parse("a=1");
+        pairs.set("b", "2");
+        assert_eq!(pairs.to_string(), "a=1&b=2");
+    }
+
+    #[test]
+    fn test_remove_works_when_typical() {
+        let mut pairs = QueryPairs::parse("a=1&b=2&a=3");
+        pairs.remove("a");
+        assert!(!pairs.contains("a"));
+        assert_eq!(pairs.len(), 1);
+    }
+
+    #[test]
+    fn test_display_works_when_special_characters() {
+        let pairs: QueryPairs = [("q", "a b&c=d/é"), ("*-._", "~")].into_iter().collect();
+        assert_eq!(pairs.to_string(), "q=a+b%26c%3Dd%2F%C3%A9&*-._=%7E");
+    }
+
+    #[test]
+    fn test_display_round_trips_when_parsed() {
+        let pairs: QueryPairs = [("a b", "1+1"), ("&", "="), ("", "")].into_iter().collect();
+        assert_eq!(QueryPairs::parse(&pairs.to_string()), pairs);
+    }
+}
@@ -19,22 +19,8 @@ impl Utils {
     /// assert_eq!(result, expected);
     /// ```
     pub fn substring_after_scheme<'a>(parser: &Parser, input: &'a str) -> &'a str {
-        let scheme = parser.scheme(input);
-        match scheme {
-            Some((v, separator)) => {
-                let start_index = v.len() + <SchemeSeparator as Into<usize>>::into(separator);
-                // Verifica se o índice está dentro dos limites da string
-                if start_index < input.len() {
-                    &input[start_index..] // Retorna a parte após o esquema
-                } else {
-                    "" // Retorna uma string vazia se o índice estiver fora dos limites
-                }
-            },
-            None => input, // Se não houver esquema, retorna a string original
-        }
+        &input[parser.tokenize(input).after_scheme..]
     }
-    
-    
 
     /// Get substring immediately after login. Eliminates scheme to ensure no colon present in remainder.
     ///
@@ -50,11 +36,7 @@ impl Utils {
     /// assert_eq!(result, expected);
     /// ```
     pub fn substring_after_login<'a>(parser: &Parser, input: &'a str) -> &'a str {
-        let input = Utils::substring_after_scheme(parser, input);
-        match input.find('@') {
-            Some(pos) => &input[pos + 1..],
-            None => input,
-        }
+        &input[parser.tokenize(input).host.start..]
     }
 
     /// Get substring immediately after port. Eliminates scheme to ensure no colon present in remainder.
@@ -71,19 +53,11 @@ impl Utils {
     /// assert_eq!(result, expected);
     /// ```
     pub fn substring_after_port<'a>(parser: &Parser, input: &'a str) -> &'a str {
-        let input = Utils::substring_after_scheme(parser, input);
-        let port = parser.port(input);
-
-        if input.find(':').is_some() {
-            let (pos_port, len_port_string) = match port {
-                Some(v) => (input.find(&v.to_string()).unwrap(), v.to_string().len() + 1),
-                None => (0, 0),
-            };
-
-            let substring_after_port = input.get(pos_port + len_port_string..);
-            return substring_after_port.unwrap_or_default();
+        let tokens = parser.tokenize(input);
+        match tokens.port_digits(input) {
+            Some(port) => input.get(port.end + 1..).unwrap_or_default(),
+            None => &input[tokens.after_scheme..],
         }
-        input
     }
 
     /// Get substring immediately before port.
@@ -99,28 +73,11 @@ impl Utils {
     /// assert_eq!(result, expected);
     /// ```
     pub fn substring_before_port<'a>(parser: &Parser, input: &'a str) -> &'a str {
-        let port = parser.port(input);
-    
-        // Determine a posição do port
-        let pos_port = match port {
-            Some(v) => {
-                // Tenta encontrar a posição do port; se não for encontrado, retorna o comprimento da string
-                match input.find(&v.to_string()) {
-                    Some(pos) if pos > 0 => pos - 1, // Subtrai 1 se a posição for maior que 0
-                    _ => return "", // Retorna uma string vazia se não encontrar ou se pos for 0
-                }
-            },
-            None => input.len(), // Se não houver port, usa o comprimento total da string
-        };
-    
-        // Retorna a substring antes da posição do port, se for válida
-        if pos_port > 0 { // Verifica se pos_port é maior que 0 para evitar slice inválido
-            &input[..pos_port]
-        } else {
-            "" // Retorna uma string vazia se pos_port não for válido
+        match parser.tokenize(input).port {
+            Some(port) => &input[..port.start - 1],
+            None => input,
         }
     }
-    
 
     /// Get substring starting at path field. Eliminates scheme to ensure no colon present in remainder.
     ///
@@ -137,11 +94,8 @@ impl Utils {
     /// assert_eq!(result, expected);
     /// ```
     pub fn substring_from_path_begin<'a>(parser: &Parser, input: &'a str) -> Option<&'a str> {
-        let input = Utils::substring_after_scheme(parser, input);
-        match input.find('/') {
-            Some(pos) => Some(&input[pos..]),
-            None => None,
-        }
+        let path = parser.tokenize(input).path?;
+        Some(&input[path.start..])
     }
 
     /// Partially matches a subpath in a path. Useful for i.e. GitHub absolute paths from URL hrefs.
@@ -0,0 +1,114 @@
+use proptest::prelude::*;
+use url_parse::core::host::Host;
+use url_parse::core::Parser;
+use url_parse::utils::Utils;
+
+#[derive(Debug, Clone)]
+struct Parts {
+    scheme: String,
+    user: Option<String>,
+    pass: Option<String>,
+    host: String,
+    port: Option<u16>,
+    path: Vec<String>,
+    query: Option<String>,
+    fragment: Option<String>,
+}
+
+impl Parts {
+    fn url(&self) -> String {
+        let mut url = format!("{}://", self.scheme);
+        if let Some(user) = &self.user {
+            url += user;
+            if let Some(pass) = &self.pass {
+                url += &format!(":{}", pass);
+            }
+            url += "@";
+        }
+        url += &self.host;
+        if let Some(port) = self.port {
+            url += &format!(":{}", port);
+        }
+        url += &self
+            .path
+            .iter()
+            .map(|segment| format!("/{}", segment))
+            .collect::<String>();
+        if let Some(query) = &self.query {
+            url += &format!("?{}", query);
+        }
+        if let Some(fragment) = &self.fragment {
+            url += &format!("#{}", fragment);
+        }
+        url
+    }
+}
+
+/// The username may contain `@` but not `:`, which would end it. The password may contain both.
+fn login() -> impl Strategy<Value = (Option<String>, Option<String>)> {
+    let user = "([a-z0-9@]|%3A|%40){1,8}";
+    let pass = "([a-z0-9@:]|%3A|%40){0,8}";
+    proptest::option::of((user, proptest::option::of(pass)))
+        .prop_map(|login| login.map_or((None, None), |(user, pass)| (Some(user), pass)))
+}
+
+fn parts() -> impl Strategy<Value = Parts> {
+    (
+        prop_oneof![Just("http"), Just("https"), Just("ftp"), Just("ssh")],
+        login(),
+        "[a-z][a-z0-9]{0,7}(\\.[a-z]{2,3}){0,2}",
+        proptest::option::of(1..=u16::MAX),
+        proptest::collection::vec("[a-z0-9@:]{0,8}", 1..4),
+        proptest::option::of("[a-z0-9@:=&]{1,12}"),
+        proptest::option::of("[a-z0-9@:/?]{1,8}"),
+    )
+        .prop_map(
+            |(scheme, (user, pass), host, port, path, query, fragment)| Parts {
+                scheme: scheme.to_string(),
+                user,
+                pass,
+                host,
+                port,
+                path,
+                query,
+                fragment,
+            },
+        )
+}
+
+fn decode(input: &Option<String>) -> Option<String> {
+    input
+        .as_deref()
+        .map(|input| input.replace("%3A", ":").replace("%40", "@"))
+}
+
+proptest! {
+    #[test]
+    fn test_components_agree_when_login_contains_at_and_colon(parts in parts()) {
+        let input = parts.url();
+        let parser = Parser::new(None);
+        let login = (parts.user.as_deref(), parts.pass.as_deref());
+
+        prop_assert_eq!(parser.login(&input), login);
+        prop_assert!(Utils::substring_after_login(&parser, &input).starts_with(&parts.host));
+        prop_assert_eq!(parser.host(&input), Some(Host::Domain(parts.host.clone())));
+        let path = parts.path.iter().map(String::as_str).collect();
+        prop_assert_eq!(parser.path(&input), Some(path));
+        prop_assert_eq!(parser.query(&input), parts.query.as_deref());
+        prop_assert_eq!(parser.anchor(&input), parts.fragment.as_deref());
+
+        let parsed = parser.parse(&input).unwrap();
+        prop_assert_eq!(&parsed.user_pass, &(parts.user.clone(), parts.pass.clone()));
+        prop_assert_eq!(parsed.port, parts.port);
+        if parts.port.is_some() {
+            prop_assert_eq!(parser.port(&input), parts.port);
+        }
+        prop_assert_eq!(parsed.username_decoded(), decode(&parts.user));
+        prop_assert_eq!(parsed.password_decoded(), decode(&parts.pass));
+
+        let borrowed = parser.parse_ref(&input).unwrap();
+        prop_assert_eq!((borrowed.username(), borrowed.password()), login);
+        prop_assert_eq!(borrowed.username_decoded().map(|user| user.into_owned()), decode(&parts.user));
+        prop_assert_eq!(borrowed.password_decoded().map(|pass| pass.into_owned()), decode(&parts.pass));
+    }
+}
@@ -0,0 +1,142 @@
+use proptest::prelude::*;
+use url_parse::core::Parser;
+
+fn scheme() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just("http"),
+        Just("https"),
+        Just("ftp"),
+        Just("ssh"),
+        Just("s3"),
+        Just("git+ssh"),
+    ]
+    .prop_map(str::to_string)
+}
+
+fn userinfo() -> impl Strategy<Value = Option<String>> {
+    let part = "([a-z0-9]|%40|%3A){1,8}";
+    proptest::option::of((part, proptest::option::of(part))).prop_map(|userinfo| {
+        userinfo.map(|(user, pass)| match pass {
+            Some(pass) => format!("{}:{}@", user, pass),
+            None => format!("{}@", user),
+        })
+    })
+}
+
+fn host() -> impl Strategy<Value = String> {
+    let domain = (
+        proptest::collection::vec("[a-z][a-z0-9]{0,7}", 1..4),
+        prop_oneof![Just(""), Just(".com"), Just(".co.uk"), Just(".io")],
+    )
+        .prop_map(|(labels, suffix)| labels.join(".") + suffix);
+    let ipv4 = any::<[u8; 4]>().prop_map(|[a, b, c, d]| format!("{}.{}.{}.{}", a, b, c, d));
+    let ipv6 = any::<u16>().prop_map(|n| format!("[2001:db8::{:x}]", n));
+    prop_oneof![4 => domain, 1 => ipv4, 1 => ipv6]
+}
+
+fn url() -> impl Strategy<Value = String> {
+    (
+        scheme(),
+        userinfo(),
+        host(),
+        proptest::option::of(1..=u16::MAX),
+        proptest::collection::vec("([a-zA-Z0-9_~-]|%2F|%20){1,8}", 0..4),
+        any::<bool>(),
+        proptest::option::of("[a-z0-9=&]{0,12}"),
+        proptest::option::of("[a-z0-9/?]{1,8}"),
+    )
+        .prop_map(
+            |(scheme, userinfo, host, port, segments, trailing_slash, query, fragment)| {
+                let mut url = format!("{}://{}{}", scheme, userinfo.unwrap_or_default(), host);
+                if let Some(port) = port {
+                    url += &format!(":{}", port);
+                }
+                url += "/";
+                url += &segments.join("/");
+                if trailing_slash && !segments.is_empty() {
+                    url += "/";
+                }
+                if let Some(query) = query {
+                    url += &format!("?{}", query);
+                }
+                if let Some(fragment) = fragment {
+                    url += &format!("#{}", fragment);
+                }
+                url
+            },
+        )
+}
+
+type Userinfo = Option<(String, Option<String>)>;
+
+/// Userinfo with the reserved characters which may appear unescaped: the username may contain `@`, as the last `@`
+/// ends the userinfo, and the password `@` and `:`, as the first `:` ends the username.
+fn raw_userinfo() -> impl Strategy<Value = Userinfo> {
+    let user = "[a-z0-9!$&'()*+,;=@-]{1,8}";
+    let pass = "[a-z0-9!$&'()*+,;=@:-]{0,8}";
+    proptest::option::of((user, proptest::option::of(pass)))
+}
+
+fn raw_url() -> impl Strategy<Value = (String, Userinfo, Vec<String>)> {
+    let segment = "[a-zA-Z0-9!$&'()*+,;=:@_~-]{0,8}";
+    (
+        scheme(),
+        raw_userinfo(),
+        host(),
+        proptest::collection::vec(segment, 1..4),
+        proptest::option::of("[a-z0-9!$&'()*+,;=:@/?]{0,12}"),
+        proptest::option::of("[a-z0-9!$&'()*+,;=:@/?]{0,8}"),
+    )
+        .prop_map(|(scheme, userinfo, host, segments, query, fragment)| {
+            let mut url = format!("{}://", scheme);
+            if let Some((user, pass)) = &userinfo {
+                url += user;
+                if let Some(pass) = pass {
+                    url += &format!(":{}", pass);
+                }
+                url += "@";
+            }
+            url += &host;
+            url += &segments
+                .iter()
+                .map(|segment| format!("/{}", segment))
+                .collect::<String>();
+            if let Some(query) = query {
+                url += &format!("?{}", query);
+            }
+            if let Some(fragment) = fragment {
+                url += &format!("#{}", fragment);
+            }
+            (url, userinfo, segments)
+        })
+}
+
+proptest! {
+    #[test]
+    fn test_serialize_round_trips_when_raw_reserved_characters((input, userinfo, segments) in raw_url()) {
+        let parser = Parser::new(None);
+        let parsed = parser.parse(&input).unwrap();
+        let serialized = parsed.serialize();
+        let reparsed = parser.parse(&serialized).unwrap();
+
+        let (user, pass) = userinfo.map_or((None, None), |(user, pass)| (Some(user), pass));
+        prop_assert_eq!(parsed.username_decoded(), user);
+        prop_assert_eq!(parsed.password_decoded(), pass);
+        prop_assert_eq!(reparsed.username_decoded(), parsed.username_decoded());
+        prop_assert_eq!(reparsed.password_decoded(), parsed.password_decoded());
+        prop_assert_eq!(reparsed.path_segments_decoded(), Some(segments));
+        prop_assert_eq!(&reparsed.query, &parsed.query);
+        prop_assert_eq!(&reparsed.anchor, &parsed.anchor);
+        prop_assert_eq!(reparsed.serialize(), serialized);
+    }
+
+    #[test]
+    fn test_serialize_round_trips_when_normalized(input in url()) {
+        let parser = Parser::new(None);
+        let parsed = parser.parse(&input).unwrap();
+        let serialized = parsed.serialize();
+
+        prop_assert_eq!(&serialized, &input);
+        prop_assert_eq!(parser.parse(&serialized).unwrap(), parsed);
+    }
+}
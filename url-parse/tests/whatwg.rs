@@ -0,0 +1,119 @@
+//! Runs `Parser::whatwg` against the web-platform-tests URL vectors in `urltestdata.json`.
+//! Inputs listed in `whatwg_expected_failures.txt` are known not to conform yet.
+
+use serde_json::Value;
+use url_parse::core::Parser;
+use url_parse::url::Url;
+
+fn hostname(url: &Url) -> String {
+    let mut result = String::new();
+    if let Some(subdomain) = &url.subdomain {
+        result += subdomain;
+        result += ".";
+    }
+    result += url.domain.as_deref().unwrap_or_default();
+    if let Some(top_level_domain) = &url.top_level_domain {
+        result += ".";
+        result += top_level_domain;
+    }
+    result
+}
+
+fn pathname(url: &Url) -> String {
+    match (&url.opaque_path, &url.path) {
+        (Some(opaque_path), _) => opaque_path.clone(),
+        (None, Some(path)) => path.iter().map(|s| format!("/{}", s)).collect(),
+        (None, None) => String::new(),
+    }
+}
+
+fn prefixed(prefix: &str, value: &Option<String>) -> String {
+    match value {
+        Some(v) if !v.is_empty() => format!("{}{}", prefix, v),
+        _ => String::new(),
+    }
+}
+
+/// Compare every component the test vector expects, returning a description of the first mismatch.
+fn check(test: &Value) -> Result<(), String> {
+    let parser = Parser::new(None);
+    let input = test["input"].as_str().unwrap();
+    let base = match test["base"].as_str() {
+        Some(base) => match parser.whatwg(base, None) {
+            Ok(base) => Some(base),
+            Err(_) if test["failure"].as_bool() == Some(true) => return Ok(()),
+            Err(_) => return Err(format!("base {:?} failed to parse", base)),
+        },
+        None => None,
+    };
+    let url = parser.whatwg(input, base.as_ref());
+
+    if test["failure"].as_bool() == Some(true) {
+        return match url {
+            Ok(url) => Err(format!("expected failure, got {:?}", url.serialize())),
+            Err(_) => Ok(()),
+        };
+    }
+    let url = url.map_err(|e| format!("unexpected {}", e))?;
+
+    let port = url.port.map(|p| p.to_string()).unwrap_or_default();
+    let host = match url.port {
+        Some(_) => format!("{}:{}", hostname(&url), port),
+        None => hostname(&url),
+    };
+    let actual = [
+        ("href", url.serialize()),
+        (
+            "protocol",
+            format!("{}:", url.scheme.clone().unwrap_or_default()),
+        ),
+        ("username", url.user_pass.0.clone().unwrap_or_default()),
+        ("password", url.user_pass.1.clone().unwrap_or_default()),
+        ("host", host),
+        ("hostname", hostname(&url)),
+        ("port", port),
+        ("pathname", pathname(&url)),
+        ("search", prefixed("?", &url.query)),
+        ("hash", prefixed("#", &url.anchor)),
+    ];
+    for (key, value) in actual {
+        let expected = test[key].as_str().unwrap();
+        if value != expected {
+            return Err(format!("{}: expected {:?}, got {:?}", key, expected, value));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_whatwg_conforms_to_urltestdata() {
+    let tests: Vec<Value> = serde_json::from_str(include_str!("urltestdata.json")).unwrap();
+    let expected_failures: Vec<&str> = include_str!("whatwg_expected_failures.txt")
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+
+    let mut unexpected = vec![];
+    let mut passed = 0;
+    for test in tests.iter().filter(|t| t.is_object()) {
+        let input = test["input"].as_str().unwrap();
+        let escaped = input.escape_debug().to_string();
+        let is_expected_failure = expected_failures.contains(&escaped.as_str());
+        match check(test) {
+            Ok(()) if is_expected_failure => unexpected.push(format!(
+                "{}\n    passes but is listed as expected failure",
+                escaped
+            )),
+            Err(e) if !is_expected_failure => unexpected.push(format!("{}\n    {}", escaped, e)),
+            Ok(()) => passed += 1,
+            Err(_) => {}
+        }
+    }
+    assert!(passed > 0);
+    assert!(
+        unexpected.is_empty(),
+        "{} unexpected results:\n{}",
+        unexpected.len(),
+        unexpected.join("\n")
+    );
+}